@@ -35,6 +35,29 @@ export class AiBlogInfrastructureStack extends cdk.Stack {
       sortKey: { name: 'created_at', type: dynamodb.AttributeType.STRING },
     });
 
+    // GSI for slug lookups (uniqueness checks and public slug URLs)
+    articlesTable.addGlobalSecondaryIndex({
+      indexName: 'slug-index',
+      partitionKey: { name: 'slug', type: dynamodb.AttributeType.STRING },
+      projectionType: dynamodb.ProjectionType.KEYS_ONLY,
+    });
+
+    // Scrape run reports (one item per scraper invocation)
+    const scrapeRunsTable = new dynamodb.Table(this, 'ScrapeRunsTable', {
+      tableName: 'ScrapeRunsTable',
+      partitionKey: { name: 'run_id', type: dynamodb.AttributeType.STRING },
+      billingMode: dynamodb.BillingMode.PAY_PER_REQUEST,
+      removalPolicy: cdk.RemovalPolicy.RETAIN,
+    });
+
+    // Per-parser listing watermarks (newest date seen by the last top-N scrape)
+    const scrapeWatermarksTable = new dynamodb.Table(this, 'ScrapeWatermarksTable', {
+      tableName: 'ScrapeWatermarksTable',
+      partitionKey: { name: 'parser', type: dynamodb.AttributeType.STRING },
+      billingMode: dynamodb.BillingMode.PAY_PER_REQUEST,
+      removalPolicy: cdk.RemovalPolicy.RETAIN,
+    });
+
     // ========================================
     // S3 Buckets
     // ========================================
@@ -190,8 +213,11 @@ export class AiBlogInfrastructureStack extends cdk.Stack {
 
     // Grant permissions to Lambda role
     articlesTable.grantReadWriteData(lambdaExecutionRole);
+    scrapeRunsTable.grantReadWriteData(lambdaExecutionRole);
+    scrapeWatermarksTable.grantReadWriteData(lambdaExecutionRole);
     contentBucket.grantReadWrite(lambdaExecutionRole);
     snapshotBucket.grantRead(lambdaExecutionRole);
+    snapshotBucket.grantPut(lambdaExecutionRole, 'debug/*');
     publicSiteBucket.grantReadWrite(lambdaExecutionRole);
 
     // ========================================
@@ -210,11 +236,14 @@ export class AiBlogInfrastructureStack extends cdk.Stack {
       memorySize: 256,
       environment: {
         TABLE_NAME: articlesTable.tableName,
+        RUNS_TABLE_NAME: scrapeRunsTable.tableName,
+        WATERMARKS_TABLE_NAME: scrapeWatermarksTable.tableName,
         BUCKET_NAME: contentBucket.bucketName,
         RUST_LOG: 'debug',
         // Provide SCRAPEDO_TOKEN via environment/secrets at deploy time.
         SCRAPEDO_TOKEN: process.env.SCRAPEDO_TOKEN ?? '',
         SNAPSHOT_BUCKET: snapshotBucket.bucketName,
+        SNAPSHOT_ON_PARSE_FAILURE: process.env.SNAPSHOT_ON_PARSE_FAILURE ?? 'false',
       },
     });
 
@@ -227,6 +256,11 @@ export class AiBlogInfrastructureStack extends cdk.Stack {
       description: 'DynamoDB Articles Table Name',
     });
 
+    new cdk.CfnOutput(this, 'ScrapeRunsTableName', {
+      value: scrapeRunsTable.tableName,
+      description: 'DynamoDB table of scrape run reports',
+    });
+
     new cdk.CfnOutput(this, 'ContentBucketName', {
       value: contentBucket.bucketName,
       description: 'S3 Content Bucket Name',
@@ -1,21 +1,43 @@
 use anyhow::Result;
 use std::env;
 
+const DEFAULT_TARGET_LANGUAGES: &str = "es,uk";
+/// Per-object cap for the article HTML and text uploaded to S3, so an archive-sized
+/// page isn't stored (and served) as a multi-megabyte article. DynamoDB only holds
+/// the metadata record, so its 400KB item limit is not what this guards.
+const DEFAULT_MAX_CONTENT_BYTES: usize = 150_000;
+
 #[derive(Debug, Clone)]
 pub struct Config {
     pub table_name: String,
+    pub runs_table_name: String,
+    /// Per-parser listing watermarks, kept out of the runs table so run queries see only runs.
+    pub watermarks_table_name: String,
     #[allow(dead_code)]
     pub bucket_name: String,
     #[allow(dead_code)]
     pub auto_publish: bool,
     #[allow(dead_code)]
     pub max_articles_per_site: usize,
+    /// Language codes articles are translated into (source content is English).
+    #[allow(dead_code)]
+    pub target_languages: Vec<String>,
+    /// Private bucket holding provided-listing HTML and debug snapshots.
+    pub snapshot_bucket: Option<String>,
+    /// Upload the raw HTML of pages that parse to an empty title/content.
+    pub snapshot_on_parse_failure: bool,
+    /// Longest `content.html` / `content.txt` uploaded, in bytes; longer content is truncated.
+    pub max_content_bytes: usize,
 }
 
 impl Config {
     pub fn from_env() -> Result<Self> {
         Ok(Self {
             table_name: env::var("TABLE_NAME").unwrap_or_else(|_| "ArticlesTable".to_string()),
+            runs_table_name: env::var("RUNS_TABLE_NAME")
+                .unwrap_or_else(|_| "ScrapeRunsTable".to_string()),
+            watermarks_table_name: env::var("WATERMARKS_TABLE_NAME")
+                .unwrap_or_else(|_| "ScrapeWatermarksTable".to_string()),
             bucket_name: env::var("BUCKET_NAME")
                 .unwrap_or_else(|_| "blog-content-bucket".to_string()),
             auto_publish: env::var("AUTO_PUBLISH")
@@ -26,6 +48,56 @@ impl Config {
                 .unwrap_or_else(|_| "10".to_string())
                 .parse()
                 .unwrap_or(10),
+            target_languages: parse_languages(
+                &env::var("TARGET_LANGUAGES")
+                    .unwrap_or_else(|_| DEFAULT_TARGET_LANGUAGES.to_string()),
+            ),
+            snapshot_bucket: env::var("SNAPSHOT_BUCKET").ok(),
+            snapshot_on_parse_failure: env::var("SNAPSHOT_ON_PARSE_FAILURE")
+                .unwrap_or_else(|_| "false".to_string())
+                .parse()
+                .unwrap_or(false),
+            max_content_bytes: max_content_bytes(),
         })
     }
 }
+
+/// `MAX_CONTENT_BYTES`, falling back to the default when unset or not a positive number.
+fn max_content_bytes() -> usize {
+    env::var("MAX_CONTENT_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .filter(|&n| n > 0)
+        .unwrap_or(DEFAULT_MAX_CONTENT_BYTES)
+}
+
+/// Parse a comma-separated list of language codes, normalizing case and
+/// dropping blanks/duplicates while keeping the configured order.
+fn parse_languages(raw: &str) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for code in raw.split(',').map(|c| c.trim().to_ascii_lowercase()) {
+        if !code.is_empty() && !out.contains(&code) {
+            out.push(code);
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_four_language_config() {
+        assert_eq!(
+            parse_languages("es,UK, fr,de,es"),
+            vec!["es", "uk", "fr", "de"]
+        );
+    }
+
+    #[test]
+    fn drops_blank_language_codes() {
+        assert_eq!(parse_languages(" , es,,"), vec!["es"]);
+        assert!(parse_languages("").is_empty());
+    }
+}
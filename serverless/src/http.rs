@@ -0,0 +1,179 @@
+use std::fmt;
+
+use anyhow::{Context, Result};
+use reqwest::{
+    header::{HeaderMap, HeaderValue, CONTENT_TYPE, FROM},
+    Client, ClientBuilder, Response,
+};
+use tracing::warn;
+
+/// Default cap on a fetched response body (10MB).
+pub const DEFAULT_MAX_BODY_BYTES: usize = 10 * 1024 * 1024;
+
+/// User agent sent when `SCRAPER_USER_AGENT` is unset.
+pub const DEFAULT_USER_AGENT: &str = "Mozilla/5.0 (compatible; BlogScraper/1.0)";
+
+/// Shared HTTP client for parsers and crawlers. The user agent comes from
+/// `SCRAPER_USER_AGENT` (default `DEFAULT_USER_AGENT`); `SCRAPER_CONTACT_EMAIL`, when
+/// set, is sent as the `From` header so site operators can reach us. A contact that
+/// is not a valid header value is dropped with a warning rather than failing.
+pub fn build_http_client() -> Result<Client> {
+    http_client_builder()
+        .build()
+        .context("failed to build reqwest client")
+}
+
+/// The `build_http_client` identity (user agent and `From` header) as a builder, for
+/// callers that need extra settings such as timeouts or a redirect policy.
+pub fn http_client_builder() -> ClientBuilder {
+    let user_agent =
+        std::env::var("SCRAPER_USER_AGENT").unwrap_or_else(|_| DEFAULT_USER_AGENT.to_string());
+
+    let mut headers = HeaderMap::new();
+    if let Some(contact) = std::env::var("SCRAPER_CONTACT_EMAIL")
+        .ok()
+        .filter(|c| !c.trim().is_empty())
+    {
+        match HeaderValue::from_str(contact.trim()) {
+            Ok(value) => {
+                headers.insert(FROM, value);
+            }
+            Err(e) => warn!(
+                "SCRAPER_CONTACT_EMAIL {:?} is not a valid header value ({}); sending no From header",
+                contact, e
+            ),
+        }
+    }
+
+    Client::builder()
+        .user_agent(user_agent)
+        .default_headers(headers)
+}
+
+/// Typed fetch failures callers may want to tell apart (e.g. to skip an article
+/// cleanly) via `anyhow::Error::downcast_ref::<FetchError>()`.
+#[derive(Debug)]
+pub enum FetchError {
+    BodyTooLarge { url: String, limit: usize },
+    UnexpectedContentType { url: String, content_type: String },
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FetchError::BodyTooLarge { url, limit } => {
+                write!(f, "response body from {} exceeds {} bytes", url, limit)
+            }
+            FetchError::UnexpectedContentType { url, content_type } => {
+                write!(f, "expected HTML from {} but got {}", url, content_type)
+            }
+        }
+    }
+}
+
+impl std::error::Error for FetchError {}
+
+/// Body size cap from `MAX_RESPONSE_BYTES`, defaulting to `DEFAULT_MAX_BODY_BYTES`.
+pub fn max_body_bytes() -> usize {
+    std::env::var("MAX_RESPONSE_BYTES")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_MAX_BODY_BYTES)
+}
+
+/// Whether `err` (or anything it wraps) is a `FetchError::UnexpectedContentType`,
+/// i.e. the URL pointed at a PDF/JSON/etc. rather than an HTML page.
+pub fn is_unexpected_content_type(err: &anyhow::Error) -> bool {
+    matches!(
+        err.downcast_ref::<FetchError>(),
+        Some(FetchError::UnexpectedContentType { .. })
+    )
+}
+
+/// Reject responses whose `Content-Type` is present and not HTML/XHTML.
+pub fn ensure_html(res: &Response) -> Result<()> {
+    check_content_type(
+        res.url().as_str(),
+        res.headers()
+            .get(CONTENT_TYPE)
+            .and_then(|v| v.to_str().ok()),
+    )
+}
+
+/// `ensure_html` for a content type obtained some other way (e.g. from a browser).
+/// A missing header is accepted, since plenty of servers omit it for HTML.
+pub fn check_content_type(url: &str, content_type: Option<&str>) -> Result<()> {
+    let Some(content_type) = content_type else {
+        return Ok(());
+    };
+
+    let mime = content_type
+        .split(';')
+        .next()
+        .unwrap_or_default()
+        .trim()
+        .to_ascii_lowercase();
+    if mime == "text/html" || mime == "application/xhtml+xml" {
+        return Ok(());
+    }
+
+    Err(FetchError::UnexpectedContentType {
+        url: url.to_string(),
+        content_type: content_type.to_string(),
+    }
+    .into())
+}
+
+/// Validate that a response is HTML, then read it with the configured size cap.
+pub async fn read_html(res: Response) -> Result<String> {
+    ensure_html(&res)?;
+    read_body_capped(res, max_body_bytes()).await
+}
+
+/// Stream a response body into a string, failing with `FetchError::BodyTooLarge`
+/// as soon as it exceeds `max_bytes` instead of buffering the whole thing.
+pub async fn read_body_capped(mut res: Response, max_bytes: usize) -> Result<String> {
+    let url = res.url().to_string();
+
+    if res
+        .content_length()
+        .is_some_and(|len| len > max_bytes as u64)
+    {
+        return Err(FetchError::BodyTooLarge {
+            url,
+            limit: max_bytes,
+        }
+        .into());
+    }
+
+    let mut body = Vec::new();
+    while let Some(chunk) = res.chunk().await? {
+        if body.len() + chunk.len() > max_bytes {
+            return Err(FetchError::BodyTooLarge {
+                url,
+                limit: max_bytes,
+            }
+            .into());
+        }
+        body.extend_from_slice(&chunk);
+    }
+
+    Ok(String::from_utf8_lossy(&body).into_owned())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn content_type_check_matches_mime_only() {
+        let url = "https://example.com/a";
+        assert!(check_content_type(url, None).is_ok());
+        assert!(check_content_type(url, Some("text/html; charset=utf-8")).is_ok());
+        assert!(check_content_type(url, Some("TEXT/HTML")).is_ok());
+
+        let err = check_content_type(url, Some("application/pdf")).unwrap_err();
+        assert!(is_unexpected_content_type(&err));
+        assert!(check_content_type(url, Some("application/json")).is_err());
+    }
+}
@@ -1,18 +1,30 @@
+use std::collections::HashMap;
+use std::time::Instant;
+
 use anyhow::Context;
+use chrono::Utc;
 use lambda_runtime::{run, service_fn, Error, LambdaEvent};
 use parser::utils::setup_tracing;
 use serde::{Deserialize, Serialize};
 use tracing::{error, info};
 
-use parser::models::Site;
+use parser::metrics::{emit_linkcheck_metrics, emit_run_metrics};
+use parser::models::{ScrapeReport, ScrapeResults, Site, SiteResult};
 use parser::services::fallback_crawler::FallbackCrawlerService;
+use parser::services::linkcheck::{LinkCheckService, LinkCheckSummary};
 use parser::services::playwright_crawler::PlaywrightCrawlerService;
 use parser::services::scrapedo_crawler::ScrapedoCrawlerService;
 use parser::services::scraper::ScraperService;
+use parser::storage::Storage;
+
+/// Values accepted in `Request::action`.
+const ACTIONS: &[&str] = &["scrape", "backfill", "linkcheck"];
 
 #[derive(Debug, Default, Deserialize)]
 #[serde(default)]
 pub(crate) struct Request {
+    /// Operation to run: "scrape" (default), "backfill" or "linkcheck".
+    pub action: Option<String>,
     pub service: Option<String>,
     pub sites: Vec<Site>,
 }
@@ -23,6 +35,18 @@ struct Response {
     #[serde(skip_serializing_if = "Vec::is_empty", default)]
     errors: Vec<String>,
     success: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    summary: Option<RunSummary>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    links: Option<LinkCheckSummary>,
+}
+
+/// Per-run counts returned to the caller of a scrape invocation.
+#[derive(Serialize)]
+struct RunSummary {
+    new_articles: usize,
+    skipped: usize,
+    sites: HashMap<String, SiteResult>,
 }
 
 #[tokio::main]
@@ -33,73 +57,179 @@ async fn main() -> Result<(), Error> {
 }
 
 async fn function_handler(event: LambdaEvent<Request>) -> Result<Response, Error> {
-    let (request, _context) = event.into_parts();
+    let (request, context) = event.into_parts();
     info!("Starting blog scraper {:?}", request);
 
-    let result: anyhow::Result<Response> = async {
-        let service_name = request
-            .service
-            .as_deref()
-            .unwrap_or("scraper")
-            .to_lowercase();
-
-        match service_name.as_str() {
-            "playwright" | "playwright-crawler" => {
-                let service = PlaywrightCrawlerService::new()
-                    .await
-                    .context("init playwright crawler service")?;
-                service
-                    .execute(&request.sites)
-                    .await
-                    .context("playwright execution")?;
-            }
-            "scrapedo" | "scrape.do" | "scrape-do" => {
-                let service = ScrapedoCrawlerService::new()
-                    .await
-                    .context("init scrape.do crawler service")?;
-                service
-                    .execute(&request.sites)
-                    .await
-                    .context("scrape.do execution")?;
-            }
-            "fallback" => {
-                let service = FallbackCrawlerService::new()
-                    .await
-                    .context("init fallback crawler service")?;
-                service
-                    .execute(&request.sites)
-                    .await
-                    .context("fallback execution")?;
-            }
-            "jordangonzalez" | _ => {
-                let service = ScraperService::new()
-                    .await
-                    .context("init scraper service")?;
-                service
-                    .execute(&request.sites)
-                    .await
-                    .context("scraper execution")?;
-            }
-        };
-
-        Ok(Response {
-            message: "Scraping completed successfully".to_string(),
-            success: true,
-            errors: vec![],
-        })
-    }
-    .await;
+    let action = request.action.as_deref().unwrap_or("scrape").to_lowercase();
+
+    let result = match action.as_str() {
+        "scrape" => run_scrape(&request, &context.request_id).await,
+        "backfill" => run_backfill().await,
+        "linkcheck" => run_linkcheck().await,
+        unknown => {
+            error!("Unknown action {:?}", unknown);
+            return Ok(Response {
+                message: format!(
+                    "Unknown action \"{}\"; expected one of: {}",
+                    unknown,
+                    ACTIONS.join(", ")
+                ),
+                success: false,
+                errors: vec![],
+                summary: None,
+                links: None,
+            });
+        }
+    };
 
     match result {
         Ok(resp) => Ok(resp),
         Err(e) => {
+            let message = match action.as_str() {
+                "backfill" => "Backfill failed",
+                "linkcheck" => "Link check failed",
+                _ => "Scraping failed",
+            };
             // Emit a clear failure line for log-based alerting, but keep the response minimal.
-            error!("Scraping failed (response will remain minimal): {:?}", e);
+            error!("{} (response will remain minimal): {:?}", message, e);
             Ok(Response {
-                message: "Scraping failed".to_string(),
+                message: message.to_string(),
                 success: false,
                 errors: vec![],
+                summary: None,
+                links: None,
             })
         }
     }
 }
+
+async fn run_scrape(request: &Request, run_id: &str) -> anyhow::Result<Response> {
+    let service_name = request
+        .service
+        .as_deref()
+        .unwrap_or("scraper")
+        .to_lowercase();
+
+    let started_at = Utc::now().timestamp();
+    let clock = Instant::now();
+    let outcome = execute_service(&service_name, &request.sites).await;
+
+    let (results, failure) = match outcome {
+        Ok(results) => (results, None),
+        Err(e) => (ScrapeResults::default(), Some(e)),
+    };
+    let mut report = ScrapeReport::from_results(run_id, &service_name, started_at, &results);
+    if let Some(e) = &failure {
+        report.errors.push(format!("{:#}", e));
+        report.errored += 1;
+    }
+    save_run_report(&report).await;
+    emit_run_metrics(&report, clock.elapsed());
+
+    if let Some(e) = failure {
+        return Err(e);
+    }
+
+    Ok(Response {
+        message: "Scraping completed successfully".to_string(),
+        success: true,
+        errors: results.errors,
+        summary: Some(RunSummary {
+            new_articles: results.new_articles,
+            skipped: results.skipped,
+            sites: results.per_site,
+        }),
+        links: None,
+    })
+}
+
+async fn execute_service(service_name: &str, sites: &[Site]) -> anyhow::Result<ScrapeResults> {
+    let results = match service_name {
+        "playwright" | "playwright-crawler" => {
+            let service = PlaywrightCrawlerService::new()
+                .await
+                .context("init playwright crawler service")?;
+            service
+                .execute(sites)
+                .await
+                .context("playwright execution")?
+        }
+        "scrapedo" | "scrape.do" | "scrape-do" => {
+            let service = ScrapedoCrawlerService::new()
+                .await
+                .context("init scrape.do crawler service")?;
+            service
+                .execute(sites)
+                .await
+                .context("scrape.do execution")?
+        }
+        "fallback" => {
+            let service = FallbackCrawlerService::new()
+                .await
+                .context("init fallback crawler service")?;
+            service.execute(sites).await.context("fallback execution")?
+        }
+        _ => {
+            let service = ScraperService::new()
+                .await
+                .context("init scraper service")?;
+            service.execute(sites).await.context("scraper execution")?
+        }
+    };
+
+    Ok(results)
+}
+
+/// Best-effort: a failure to record the run must not change the run's outcome.
+async fn save_run_report(report: &ScrapeReport) {
+    let saved = async {
+        let storage = Storage::from_env().await.context("init storage")?;
+        storage.save_run_report(report).await
+    }
+    .await;
+
+    if let Err(e) = saved {
+        error!(
+            "Failed to save scrape run report run_id={}: {:?}",
+            report.run_id, e
+        );
+    }
+}
+
+async fn run_backfill() -> anyhow::Result<Response> {
+    let storage = Storage::from_env().await.context("init storage")?;
+    let summary = storage
+        .backfill_metadata()
+        .await
+        .context("metadata backfill")?;
+
+    Ok(Response {
+        message: format!(
+            "Backfill completed: {} updated, {} skipped",
+            summary.updated, summary.skipped
+        ),
+        success: true,
+        errors: vec![],
+        summary: None,
+        links: None,
+    })
+}
+
+async fn run_linkcheck() -> anyhow::Result<Response> {
+    let service = LinkCheckService::new()
+        .await
+        .context("init link check service")?;
+    let summary = service.execute().await.context("link check")?;
+    emit_linkcheck_metrics(&summary);
+
+    Ok(Response {
+        message: format!(
+            "Link check completed: {} checked, {} dead, {} redirected",
+            summary.checked, summary.dead, summary.redirect
+        ),
+        success: true,
+        errors: vec![],
+        summary: None,
+        links: Some(summary),
+    })
+}
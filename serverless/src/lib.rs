@@ -1,6 +1,11 @@
 pub mod config;
+pub mod http;
+pub mod metrics;
 pub mod models;
 pub mod parsers;
+pub mod sanitize;
 pub mod services;
+pub mod slug;
 pub mod storage;
+pub mod tags;
 pub mod utils;
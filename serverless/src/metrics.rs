@@ -0,0 +1,161 @@
+use std::time::Duration;
+
+use chrono::Utc;
+use serde_json::{json, Value};
+
+use crate::models::ScrapeReport;
+use crate::services::linkcheck::LinkCheckSummary;
+
+const DEFAULT_NAMESPACE: &str = "BlogScraper";
+
+/// Print a run report's counters as CloudWatch Embedded Metric Format records on stdout:
+/// one record with run totals (dimension `Service`) and one per site (`Service`, `Site`).
+/// CloudWatch turns these log lines into metrics without a `PutMetricData` call.
+pub fn emit_run_metrics(report: &ScrapeReport, duration: Duration) {
+    for record in run_metric_records(report, duration) {
+        println!("{}", record);
+    }
+}
+
+/// Print a link check's counts as one EMF record (dimension `Service` = `linkcheck`),
+/// so dead source links can be graphed and alarmed on.
+pub fn emit_linkcheck_metrics(summary: &LinkCheckSummary) {
+    let namespace =
+        std::env::var("METRICS_NAMESPACE").unwrap_or_else(|_| DEFAULT_NAMESPACE.to_string());
+    let record = json!({
+        "_aws": {
+            "Timestamp": Utc::now().timestamp_millis(),
+            "CloudWatchMetrics": [{
+                "Namespace": namespace,
+                "Dimensions": [["Service"]],
+                "Metrics": [
+                    { "Name": "CheckedLinks", "Unit": "Count" },
+                    { "Name": "RedirectLinks", "Unit": "Count" },
+                    { "Name": "DeadLinks", "Unit": "Count" },
+                ],
+            }],
+        },
+        "Service": "linkcheck",
+        "CheckedLinks": summary.checked,
+        "RedirectLinks": summary.redirect,
+        "DeadLinks": summary.dead,
+    });
+    println!("{}", record);
+}
+
+fn run_metric_records(report: &ScrapeReport, duration: Duration) -> Vec<Value> {
+    let namespace =
+        std::env::var("METRICS_NAMESPACE").unwrap_or_else(|_| DEFAULT_NAMESPACE.to_string());
+    let timestamp = Utc::now().timestamp_millis();
+
+    let mut records = vec![json!({
+        "_aws": {
+            "Timestamp": timestamp,
+            "CloudWatchMetrics": [{
+                "Namespace": namespace,
+                "Dimensions": [["Service"]],
+                "Metrics": [
+                    { "Name": "NewArticles", "Unit": "Count" },
+                    { "Name": "SkippedArticles", "Unit": "Count" },
+                    { "Name": "Errors", "Unit": "Count" },
+                    { "Name": "DurationMs", "Unit": "Milliseconds" },
+                ],
+            }],
+        },
+        "Service": report.service,
+        "NewArticles": report.new_articles,
+        "SkippedArticles": report.skipped,
+        "Errors": report.errored,
+        "DurationMs": duration.as_millis() as u64,
+    })];
+
+    for (site, outcome) in &report.sites {
+        records.push(json!({
+            "_aws": {
+                "Timestamp": timestamp,
+                "CloudWatchMetrics": [{
+                    "Namespace": namespace,
+                    "Dimensions": [["Service", "Site"]],
+                    "Metrics": [
+                        { "Name": "NewArticles", "Unit": "Count" },
+                        { "Name": "SkippedArticles", "Unit": "Count" },
+                        { "Name": "Errors", "Unit": "Count" },
+                    ],
+                }],
+            },
+            "Service": report.service,
+            "Site": site,
+            "NewArticles": outcome.new,
+            "SkippedArticles": outcome.skipped,
+            "Errors": outcome.errors.len(),
+        }));
+    }
+
+    records
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{ScrapeResults, SiteResult};
+
+    fn metric_names(record: &Value) -> Vec<&str> {
+        record["_aws"]["CloudWatchMetrics"][0]["Metrics"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|m| m["Name"].as_str().unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn run_records_are_emf_with_totals_and_per_site_counts() {
+        let mut results = ScrapeResults::default();
+        results.record(
+            "openai-research",
+            SiteResult {
+                new: 2,
+                skipped: 1,
+                errors: vec!["timeout".to_string()],
+                listing: None,
+            },
+        );
+        let report = ScrapeReport::from_results("run-1", "scraper", 100, &results);
+
+        let records = run_metric_records(&report, Duration::from_millis(1500));
+
+        assert_eq!(records.len(), 2);
+        for record in &records {
+            assert!(record["_aws"]["Timestamp"].is_i64());
+            assert!(record["_aws"]["CloudWatchMetrics"][0]["Namespace"].is_string());
+            for name in metric_names(record) {
+                assert!(record[name].is_u64(), "{} has no value", name);
+            }
+        }
+
+        let totals = &records[0];
+        assert_eq!(
+            metric_names(totals),
+            vec!["NewArticles", "SkippedArticles", "Errors", "DurationMs"]
+        );
+        assert_eq!(
+            totals["_aws"]["CloudWatchMetrics"][0]["Dimensions"],
+            json!([["Service"]])
+        );
+        assert_eq!(totals["Service"], "scraper");
+        assert_eq!(totals["NewArticles"], 2);
+        assert_eq!(totals["DurationMs"], 1500);
+
+        let site = &records[1];
+        assert_eq!(
+            metric_names(site),
+            vec!["NewArticles", "SkippedArticles", "Errors"]
+        );
+        assert_eq!(
+            site["_aws"]["CloudWatchMetrics"][0]["Dimensions"],
+            json!([["Service", "Site"]])
+        );
+        assert_eq!(site["Site"], "openai-research");
+        assert_eq!(site["Errors"], 1);
+    }
+}
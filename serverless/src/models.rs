@@ -1,7 +1,11 @@
-use chrono::Utc;
+use std::collections::HashMap;
+
+use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::tags::{extract_tags, DEFAULT_TAG_COUNT};
+
 #[derive(Debug, Clone, Deserialize)]
 pub struct Site {
     pub name: String,
@@ -47,20 +51,6 @@ pub struct Article {
     pub publishing: PublishingMetadata,
 }
 
-impl Default for PublishingMetadata {
-    fn default() -> Self {
-        Self {
-            staged_at: None,
-            staged_by: None,
-            published_at: None,
-            published_by: None,
-            staging_url: None,
-            production_url: None,
-            version: 0,
-        }
-    }
-}
-
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArticleContent {
     pub original_html: String,
@@ -68,11 +58,9 @@ pub struct ArticleContent {
     pub images: Vec<String>,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Translations {
-    pub es: Translation,
-    pub uk: Translation,
-}
+/// Translations keyed by language code (e.g. "es", "uk"). Serializes to the same
+/// `{ "es": {...}, "uk": {...} }` shape as the former fixed struct.
+pub type Translations = HashMap<String, Translation>;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Translation {
@@ -92,7 +80,7 @@ pub enum ArticleStatus {
     Rejected,  // Rejected by admin
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct PublishingMetadata {
     pub staged_at: Option<i64>,
     pub staged_by: Option<String>,
@@ -116,10 +104,74 @@ pub struct ListingItem {
     pub title: String,
     pub category: String,
     pub date_text: String,
+    /// Machine-readable publish date (RFC 3339 or `YYYY-MM-DD`), when the listing has one.
+    pub date_iso: Option<String>,
+}
+
+impl ListingItem {
+    pub fn published_at(&self) -> Option<DateTime<Utc>> {
+        let raw = self.date_iso.as_deref()?.trim();
+        DateTime::parse_from_rfc3339(raw)
+            .map(|d| d.with_timezone(&Utc))
+            .ok()
+            .or_else(|| {
+                NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+                    .ok()
+                    .and_then(|d| d.and_hms_opt(0, 0, 0))
+                    .map(|d| d.and_utc())
+            })
+    }
+}
+
+/// Whether a listing page looked structurally like a listing, so "no new articles"
+/// can be told apart from "the selectors broke".
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ListingOutcome {
+    /// At least one listing item was parsed.
+    Found,
+    /// The listing container/rows matched but yielded no usable items.
+    Empty,
+    /// The container selector matched nothing; the markup has likely changed.
+    SelectorMiss,
+}
+
+impl ListingOutcome {
+    pub fn classify(item_count: usize, container_found: bool) -> Self {
+        match (item_count, container_found) {
+            (0, true) => ListingOutcome::Empty,
+            (0, false) => ListingOutcome::SelectorMiss,
+            _ => ListingOutcome::Found,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ListingOutcome::Found => "found",
+            ListingOutcome::Empty => "empty",
+            ListingOutcome::SelectorMiss => "selector_miss",
+        }
+    }
+}
+
+/// Items parsed from a listing page plus how the page looked structurally.
+#[derive(Debug, Clone)]
+pub struct Listing {
+    pub items: Vec<ListingItem>,
+    pub outcome: ListingOutcome,
+}
+
+impl Listing {
+    pub fn new(items: Vec<ListingItem>, container_found: bool) -> Self {
+        let outcome = ListingOutcome::classify(items.len(), container_found);
+        Self { items, outcome }
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct ScrapedArticle {
+    /// Canonical URL declared by the page, or the fetched URL when none is declared.
+    pub canonical_url: String,
     pub title: String,
     pub author: String,
     pub published_date: String,
@@ -131,13 +183,95 @@ pub struct ScrapedArticle {
 #[derive(Debug, Default)]
 pub struct ScrapeResults {
     pub new_articles: usize,
+    /// Articles already stored, unchanged on re-scrape, or not HTML, across all sites.
+    pub skipped: usize,
     pub errors: Vec<String>,
+    /// Breakdown keyed by site name.
+    pub per_site: HashMap<String, SiteResult>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SiteResult {
+    pub new: usize,
+    pub skipped: usize,
+    pub errors: Vec<String>,
+    /// Shape of the site's listing page, when one was fetched.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub listing: Option<ListingOutcome>,
+}
+
+impl ScrapeResults {
+    /// Fold one site's outcome into the totals and the per-site breakdown.
+    pub fn record(&mut self, site: &str, outcome: SiteResult) {
+        self.new_articles += outcome.new;
+        self.skipped += outcome.skipped;
+        self.errors
+            .extend(outcome.errors.iter().map(|e| format!("{}: {}", site, e)));
+
+        let entry = self.per_site.entry(site.to_string()).or_default();
+        entry.new += outcome.new;
+        entry.skipped += outcome.skipped;
+        entry.errors.extend(outcome.errors);
+        if outcome.listing.is_some() {
+            entry.listing = outcome.listing;
+        }
+    }
+
+    /// Record a failure that stopped one site's scrape without aborting the run.
+    pub fn record_error(&mut self, site: &str, err: &anyhow::Error) {
+        let message = format!("{:#}", err);
+        self.errors.push(format!("{}: {}", site, message));
+        self.per_site
+            .entry(site.to_string())
+            .or_default()
+            .errors
+            .push(message);
+    }
+}
+
+/// Outcome of one scraper Lambda invocation, persisted to the scrape-runs table.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScrapeReport {
+    pub run_id: String,
+    pub service: String,
+    pub started_at: i64,
+    pub finished_at: i64,
+    pub new_articles: usize,
+    pub skipped: usize,
+    pub errored: usize,
+    pub sites: HashMap<String, SiteResult>,
+    pub errors: Vec<String>,
+}
+
+impl ScrapeReport {
+    /// Build a report for a run that started at `started_at` and finished now.
+    pub fn from_results(
+        run_id: &str,
+        service: &str,
+        started_at: i64,
+        results: &ScrapeResults,
+    ) -> Self {
+        let sites = results.per_site.clone();
+        let site_errors: usize = sites.values().map(|s| s.errors.len()).sum();
+
+        Self {
+            run_id: run_id.to_string(),
+            service: service.to_string(),
+            started_at,
+            finished_at: Utc::now().timestamp(),
+            new_articles: results.new_articles,
+            skipped: results.skipped,
+            errored: site_errors.max(results.errors.len()),
+            sites,
+            errors: results.errors.clone(),
+        }
+    }
 }
 
 impl Article {
     pub fn new(source: &str, source_url: &str, scraped: ScrapedArticle) -> Self {
-        let word_count = scraped.content_text.split_whitespace().count();
-        let reading_time = format!("{} min", word_count / 200);
+        let (word_count, reading_time) = reading_stats(&scraped.content_text);
+        let tags = extract_tags(&scraped.content_text, DEFAULT_TAG_COUNT);
 
         Self {
             id: Uuid::new_v4().to_string(),
@@ -157,9 +291,119 @@ impl Article {
             metadata: ArticleMetadata {
                 word_count,
                 reading_time,
-                tags: vec![],
+                tags,
             },
             publishing: PublishingMetadata::default(),
         }
     }
 }
+
+/// Longest prefix of `text` that fits in `max_bytes` without splitting a character.
+pub fn truncate_on_char_boundary(text: &str, max_bytes: usize) -> &str {
+    if text.len() <= max_bytes {
+        return text;
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
+
+/// Word count and a human reading-time estimate (200 words per minute) for article text.
+pub fn reading_stats(text: &str) -> (usize, String) {
+    let word_count = text.split_whitespace().count();
+    (word_count, format!("{} min", word_count / 200))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn truncation_respects_char_boundaries() {
+        assert_eq!(truncate_on_char_boundary("héllo", 2), "h");
+        assert_eq!(truncate_on_char_boundary("héllo", 3), "hé");
+        assert_eq!(truncate_on_char_boundary("héllo", 64), "héllo");
+    }
+
+    fn site(new: usize, skipped: usize, errors: &[&str]) -> SiteResult {
+        SiteResult {
+            new,
+            skipped,
+            errors: errors.iter().map(|e| e.to_string()).collect(),
+            listing: None,
+        }
+    }
+
+    #[test]
+    fn report_aggregates_run_totals() {
+        let mut results = ScrapeResults::default();
+        results.record("openai-research", site(2, 1, &[]));
+        results.record("openai-security", site(1, 3, &["timeout"]));
+
+        let report = ScrapeReport::from_results("run-1", "scraper", 100, &results);
+
+        assert_eq!(report.run_id, "run-1");
+        assert_eq!(report.service, "scraper");
+        assert_eq!(report.started_at, 100);
+        assert!(report.finished_at >= report.started_at);
+        assert_eq!(report.new_articles, 3);
+        assert_eq!(report.skipped, 4);
+        assert_eq!(report.errored, 1);
+        assert_eq!(report.errors, vec!["openai-security: timeout"]);
+        assert_eq!(report.sites.len(), 2);
+    }
+
+    #[test]
+    fn record_merges_per_site_breakdown() {
+        let mut results = ScrapeResults::default();
+        results.record(
+            "openai-research",
+            SiteResult {
+                listing: Some(ListingOutcome::Found),
+                ..site(2, 1, &[])
+            },
+        );
+        results.record("openai-research", site(1, 0, &["bad page"]));
+        results.record("openai-security", site(0, 4, &[]));
+
+        assert_eq!(results.new_articles, 3);
+        assert_eq!(results.skipped, 5);
+        assert_eq!(results.errors, vec!["openai-research: bad page"]);
+
+        let research = &results.per_site["openai-research"];
+        assert_eq!((research.new, research.skipped), (3, 1));
+        assert_eq!(research.errors, vec!["bad page"]);
+        // A later outcome without a listing keeps the one already seen.
+        assert_eq!(research.listing, Some(ListingOutcome::Found));
+
+        let security = &results.per_site["openai-security"];
+        assert_eq!((security.new, security.skipped), (0, 4));
+        assert!(security.errors.is_empty());
+    }
+
+    #[test]
+    fn recorded_error_leaves_other_sites_counts_alone() {
+        let mut results = ScrapeResults::default();
+        let failure = anyhow::anyhow!("listing returned 503").context("top articles scrape");
+        results.record_error("site-a", &failure);
+        results.record("site-b", site(2, 0, &[]));
+
+        assert_eq!(results.new_articles, 2);
+        assert_eq!(
+            results.errors,
+            vec!["site-a: top articles scrape: listing returned 503"]
+        );
+        assert_eq!(
+            results.per_site["site-a"].errors,
+            vec!["top articles scrape: listing returned 503"]
+        );
+        assert_eq!(results.per_site["site-a"].new, 0);
+        assert_eq!(results.per_site["site-b"].new, 2);
+
+        let report = ScrapeReport::from_results("run-2", "scraper", 0, &results);
+        assert_eq!(report.errored, 1);
+        assert_eq!(report.new_articles, 2);
+    }
+}
@@ -0,0 +1,404 @@
+use anyhow::{anyhow, Context, Result};
+use reqwest::Client;
+use scraper::{ElementRef, Html, Selector};
+use serde::Deserialize;
+use tracing::{info, warn};
+
+use crate::http::build_http_client;
+use crate::models::{Listing, ListingItem, ScrapedArticle};
+use crate::parsers::dates::parse_date_text;
+use crate::parsers::metadata::{canonical_url, extract_structured_metadata};
+use crate::parsers::{absolute_url, extract_text, prepend_hero_image, Parser};
+use crate::sanitize::sanitize_html;
+
+/// CSS selectors describing how to scrape one site without a dedicated `Parser` impl.
+///
+/// Listing selectors (`link`, `title`, `date`, `category`) are evaluated inside each
+/// `listing_item`; article selectors are evaluated against the whole article page.
+#[derive(Debug, Clone, Deserialize)]
+pub struct SiteParserConfig {
+    pub name: String,
+    pub base_url: String,
+    pub listing_url: String,
+    pub listing_item: String,
+    pub link: String,
+    pub title: String,
+    pub content: String,
+    #[serde(default)]
+    pub date: Option<String>,
+    #[serde(default)]
+    pub category: Option<String>,
+    #[serde(default = "default_article_title")]
+    pub article_title: String,
+    #[serde(default)]
+    pub author: Option<String>,
+    #[serde(default = "default_image")]
+    pub image: String,
+}
+
+fn default_article_title() -> String {
+    "h1".to_string()
+}
+
+fn default_image() -> String {
+    "img".to_string()
+}
+
+#[derive(Clone)]
+struct CompiledSelectors {
+    listing_item: Selector,
+    link: Selector,
+    title: Selector,
+    content: Selector,
+    date: Option<Selector>,
+    category: Option<Selector>,
+    article_title: Selector,
+    author: Option<Selector>,
+    image: Selector,
+}
+
+/// Generic `Parser` driven entirely by a `SiteParserConfig`.
+#[derive(Clone)]
+pub struct ConfigParser {
+    config: SiteParserConfig,
+    selectors: CompiledSelectors,
+    client: Client,
+}
+
+impl ConfigParser {
+    pub fn new(config: SiteParserConfig) -> Result<Self> {
+        let selectors = CompiledSelectors {
+            listing_item: compile(&config.listing_item)?,
+            link: compile(&config.link)?,
+            title: compile(&config.title)?,
+            content: compile(&config.content)?,
+            date: config.date.as_deref().map(compile).transpose()?,
+            category: config.category.as_deref().map(compile).transpose()?,
+            article_title: compile(&config.article_title)?,
+            author: config.author.as_deref().map(compile).transpose()?,
+            image: compile(&config.image)?,
+        };
+
+        Ok(Self {
+            config,
+            selectors,
+            client: build_http_client()?,
+        })
+    }
+}
+
+impl Parser for ConfigParser {
+    fn name(&self) -> &str {
+        &self.config.name
+    }
+
+    fn listing_url(&self) -> &str {
+        &self.config.listing_url
+    }
+
+    fn client(&self) -> &Client {
+        &self.client
+    }
+
+    fn parse_listing_from_html(&self, html: &str) -> Listing {
+        let document = Html::parse_document(html);
+        let mut out = Vec::new();
+        let mut rows_found = false;
+
+        for row in document.select(&self.selectors.listing_item) {
+            rows_found = true;
+            let Some(href) = row
+                .select(&self.selectors.link)
+                .next()
+                .and_then(|link| link.value().attr("href"))
+            else {
+                continue;
+            };
+
+            let title = first_text(&row, &self.selectors.title).unwrap_or_default();
+            if title.is_empty() {
+                continue;
+            }
+
+            let date_text = self
+                .selectors
+                .date
+                .as_ref()
+                .and_then(|sel| first_text(&row, sel))
+                .unwrap_or_default();
+            let date_iso = self
+                .selectors
+                .date
+                .as_ref()
+                .and_then(|sel| row.select(sel).next())
+                .and_then(|d| d.value().attr("datetime"))
+                .map(|s| s.to_string())
+                .or_else(|| parse_date_text(&date_text).map(|d| d.to_rfc3339()));
+            let category = self
+                .selectors
+                .category
+                .as_ref()
+                .and_then(|sel| first_text(&row, sel))
+                .unwrap_or_default();
+
+            out.push(ListingItem {
+                url: absolute_url(&self.config.base_url, href),
+                title,
+                category,
+                date_text,
+                date_iso,
+            });
+        }
+
+        let listing = Listing::new(out, rows_found);
+        if listing.items.is_empty() {
+            warn!(
+                "TAG:CONFIG_LISTING_EMPTY parser={} url={} outcome={}",
+                self.config.name,
+                self.config.listing_url,
+                listing.outcome.as_str()
+            );
+        }
+
+        listing
+    }
+
+    fn parse_article_from_html(&self, html: &str, url: &str) -> Result<ScrapedArticle> {
+        let document = Html::parse_document(html);
+        let root = document.root_element();
+        let structured = extract_structured_metadata(&document);
+
+        let title = structured
+            .title
+            .or_else(|| first_text(&root, &self.selectors.article_title))
+            .unwrap_or_else(|| "Untitled".to_string());
+
+        let published_date = structured
+            .published_date
+            .or_else(|| {
+                self.selectors
+                    .date
+                    .as_ref()
+                    .and_then(|sel| document.select(sel).next())
+                    .map(|d| {
+                        d.value()
+                            .attr("datetime")
+                            .map(|s| s.to_string())
+                            .unwrap_or_else(|| extract_text(&d))
+                    })
+            })
+            .unwrap_or_else(|| "Unknown".to_string());
+
+        let author = structured
+            .author
+            .or_else(|| {
+                self.selectors
+                    .author
+                    .as_ref()
+                    .and_then(|sel| first_text(&root, sel))
+            })
+            .unwrap_or_else(|| "Unknown".to_string());
+
+        let content_element = document
+            .select(&self.selectors.content)
+            .next()
+            .context("No content found")?;
+        let content_html = sanitize_html(&content_element.html());
+        let content_text = extract_text(&content_element);
+
+        let mut images: Vec<String> = content_element
+            .select(&self.selectors.image)
+            .filter_map(|img| img.value().attr("src"))
+            .map(|src| absolute_url(&self.config.base_url, src))
+            .collect();
+        prepend_hero_image(&mut images, structured.image, &self.config.base_url);
+
+        Ok(ScrapedArticle {
+            canonical_url: canonical_url(&document, url),
+            title,
+            author,
+            published_date,
+            content_html,
+            content_text,
+            images,
+        })
+    }
+}
+
+/// Load site parser configs from `PARSER_CONFIGS` (inline JSON array) or, if unset,
+/// from the `s3://bucket/key` object named by `PARSER_CONFIGS_URL`.
+pub async fn load_config_parsers() -> Result<Vec<ConfigParser>> {
+    let raw = if let Ok(json) = std::env::var("PARSER_CONFIGS") {
+        json
+    } else if let Ok(url) = std::env::var("PARSER_CONFIGS_URL") {
+        fetch_s3_json(&url).await?
+    } else {
+        return Ok(Vec::new());
+    };
+
+    let configs: Vec<SiteParserConfig> =
+        serde_json::from_str(&raw).context("invalid parser config JSON")?;
+    let parsers = configs
+        .into_iter()
+        .map(|config| {
+            let name = config.name.clone();
+            ConfigParser::new(config).with_context(|| format!("invalid parser config {}", name))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    info!(
+        "Loaded {} config-driven parsers: {}",
+        parsers.len(),
+        parsers
+            .iter()
+            .map(|p| p.config.name.as_str())
+            .collect::<Vec<_>>()
+            .join(", ")
+    );
+
+    Ok(parsers)
+}
+
+async fn fetch_s3_json(url: &str) -> Result<String> {
+    let (bucket, key) = url
+        .strip_prefix("s3://")
+        .and_then(|path| path.split_once('/'))
+        .ok_or_else(|| anyhow!("PARSER_CONFIGS_URL must look like s3://bucket/key"))?;
+
+    let aws_config = aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await;
+    let s3 = aws_sdk_s3::Client::new(&aws_config);
+    let obj = s3
+        .get_object()
+        .bucket(bucket)
+        .key(key)
+        .send()
+        .await
+        .with_context(|| format!("failed to fetch {}", url))?;
+    let data = obj
+        .body
+        .collect()
+        .await
+        .context("failed reading parser config body")?;
+    String::from_utf8(data.into_bytes().to_vec()).context("parser config was not valid UTF-8")
+}
+
+fn compile(selector: &str) -> Result<Selector> {
+    Selector::parse(selector).map_err(|e| anyhow!("invalid selector {:?}: {}", selector, e))
+}
+
+fn first_text(scope: &ElementRef<'_>, selector: &Selector) -> Option<String> {
+    scope
+        .select(selector)
+        .next()
+        .map(|el| extract_text(&el))
+        .filter(|s| !s.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::ListingOutcome;
+
+    fn parser() -> ConfigParser {
+        let config: SiteParserConfig = serde_json::from_value(serde_json::json!({
+            "name": "example-blog",
+            "base_url": "https://blog.example.com",
+            "listing_url": "https://blog.example.com/posts",
+            "listing_item": "article.post",
+            "link": "a",
+            "title": "h2",
+            "date": "time",
+            "category": ".tag",
+            "content": ".post-body",
+            "author": ".byline"
+        }))
+        .unwrap();
+        ConfigParser::new(config).unwrap()
+    }
+
+    #[test]
+    fn parses_listing_rows_from_config_selectors() {
+        let html = r#"<html><body>
+            <article class="post">
+              <a href="/posts/first"><h2>First post</h2></a>
+              <span class="tag">News</span>
+              <time datetime="2025-01-05T00:00:00Z">Jan 5, 2025</time>
+            </article>
+            <article class="post">
+              <a href="https://other.example.com/second"><h2>Second post</h2></a>
+              <time>March 2, 2025</time>
+            </article>
+            <article class="post"><a href="/untitled"></a></article>
+        </body></html>"#;
+
+        let listing = parser().parse_listing_from_html(html);
+
+        assert_eq!(listing.outcome, ListingOutcome::Found);
+        assert_eq!(listing.items.len(), 2);
+        let first = &listing.items[0];
+        assert_eq!(first.url, "https://blog.example.com/posts/first");
+        assert_eq!(first.title, "First post");
+        assert_eq!(first.category, "News");
+        assert_eq!(first.date_text, "Jan 5, 2025");
+        assert_eq!(first.date_iso.as_deref(), Some("2025-01-05T00:00:00Z"));
+        let second = &listing.items[1];
+        assert_eq!(second.url, "https://other.example.com/second");
+        assert_eq!(second.category, "");
+        assert_eq!(
+            second.date_iso.as_deref(),
+            Some("2025-03-02T00:00:00+00:00")
+        );
+    }
+
+    #[test]
+    fn reports_selector_miss_for_unrelated_markup() {
+        let listing = parser().parse_listing_from_html("<html><body><p>Hi</p></body></html>");
+
+        assert!(listing.items.is_empty());
+        assert_eq!(listing.outcome, ListingOutcome::SelectorMiss);
+    }
+
+    #[test]
+    fn parses_article_from_config_selectors() {
+        let html = r#"<html><head>
+            <link rel="canonical" href="https://blog.example.com/posts/first">
+        </head><body>
+            <h1>First post</h1>
+            <p class="byline">Ada Lovelace</p>
+            <time datetime="2025-01-05">Jan 5, 2025</time>
+            <div class="post-body">
+              <p>Hello <b>world</b>.</p>
+              <img src="/img/chart.png">
+              <script>alert(1)</script>
+            </div>
+        </body></html>"#;
+
+        let article = parser()
+            .parse_article_from_html(html, "https://blog.example.com/posts/first?utm=x")
+            .unwrap();
+
+        assert_eq!(
+            article.canonical_url,
+            "https://blog.example.com/posts/first"
+        );
+        assert_eq!(article.title, "First post");
+        assert_eq!(article.author, "Ada Lovelace");
+        assert_eq!(article.published_date, "2025-01-05");
+        assert_eq!(article.content_text, "Hello world .");
+        assert!(!article.content_html.contains("script"));
+        assert_eq!(
+            article.images,
+            vec!["https://blog.example.com/img/chart.png"]
+        );
+    }
+
+    #[test]
+    fn article_without_content_is_an_error() {
+        let result = parser().parse_article_from_html(
+            "<html><body><h1>Empty</h1></body></html>",
+            "https://blog.example.com/posts/empty",
+        );
+
+        assert!(result.is_err());
+    }
+}
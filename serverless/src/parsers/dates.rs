@@ -0,0 +1,169 @@
+use chrono::{DateTime, Duration, NaiveDate, Utc};
+
+/// Absolute formats seen on listing pages. chrono's `%b`/`%B` accept both short
+/// and full month names, so "Jan 5, 2025" and "January 5, 2025" share a pattern.
+const ABSOLUTE_FORMATS: &[&str] = &["%B %d, %Y", "%B %d %Y", "%d %B %Y", "%d %B, %Y", "%Y-%m-%d"];
+
+/// Parse a human listing date ("January 5, 2025", "5 Jan 2025", "2 days ago") into
+/// UTC. Every result is midnight UTC of its day, so relative dates re-parse to the
+/// same value on every run that day.
+pub fn parse_date_text(text: &str) -> Option<DateTime<Utc>> {
+    parse_date_text_from(text, Utc::now())
+}
+
+fn parse_date_text_from(text: &str, now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let cleaned = text
+        .trim()
+        .trim_end_matches('.')
+        .replace(". ", " ")
+        .replace("Sept ", "Sep ");
+    if cleaned.is_empty() {
+        return None;
+    }
+
+    ABSOLUTE_FORMATS
+        .iter()
+        .find_map(|fmt| NaiveDate::parse_from_str(&cleaned, fmt).ok())
+        .or_else(|| parse_relative(&cleaned.to_ascii_lowercase(), now))
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|d| d.and_utc())
+}
+
+fn parse_relative(text: &str, now: DateTime<Utc>) -> Option<NaiveDate> {
+    match text {
+        "just now" | "now" | "today" => return Some(now.date_naive()),
+        "yesterday" => return Some((now - Duration::days(1)).date_naive()),
+        _ => {}
+    }
+
+    let mut parts = text.strip_suffix(" ago")?.split_whitespace();
+    let amount = match parts.next()? {
+        "a" | "an" | "one" => 1,
+        n => n.parse::<i64>().ok()?,
+    };
+    let unit = parts.next()?;
+    if parts.next().is_some() {
+        return None;
+    }
+
+    let duration = match unit.trim_end_matches('s') {
+        "minute" | "min" => Duration::minutes(amount),
+        "hour" | "hr" => Duration::hours(amount),
+        "day" => Duration::days(amount),
+        "week" => Duration::weeks(amount),
+        "month" => Duration::days(amount * 30),
+        "year" => Duration::days(amount * 365),
+        _ => return None,
+    };
+
+    Some((now - duration).date_naive())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2025-03-10T15:42:07Z")
+            .unwrap()
+            .with_timezone(&Utc)
+    }
+
+    fn day(raw: &str) -> Option<DateTime<Utc>> {
+        Some(
+            NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+                .and_utc(),
+        )
+    }
+
+    #[test]
+    fn parses_each_absolute_format() {
+        let cases = [
+            ("January 5, 2025", "2025-01-05"),
+            ("Jan 5, 2025", "2025-01-05"),
+            ("Jan 5 2025", "2025-01-05"),
+            ("5 January 2025", "2025-01-05"),
+            ("5 Jan, 2025", "2025-01-05"),
+            ("2025-01-05", "2025-01-05"),
+            ("  Jan 5, 2025.  ", "2025-01-05"),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(
+                parse_date_text_from(text, now()),
+                day(expected),
+                "{:?}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn accepts_abbreviated_months_with_a_period() {
+        assert_eq!(
+            parse_date_text_from("Sep. 3, 2024", now()),
+            day("2024-09-03")
+        );
+        assert_eq!(
+            parse_date_text_from("Sept 3, 2024", now()),
+            day("2024-09-03")
+        );
+        assert_eq!(
+            parse_date_text_from("Sept. 3, 2024", now()),
+            day("2024-09-03")
+        );
+        assert_eq!(
+            parse_date_text_from("3 Dec. 2024", now()),
+            day("2024-12-03")
+        );
+    }
+
+    #[test]
+    fn relative_dates_resolve_to_midnight_of_their_day() {
+        let cases = [
+            ("just now", "2025-03-10"),
+            ("Today", "2025-03-10"),
+            ("yesterday", "2025-03-09"),
+            ("30 minutes ago", "2025-03-10"),
+            ("16 hours ago", "2025-03-09"),
+            ("a day ago", "2025-03-09"),
+            ("2 days ago", "2025-03-08"),
+            ("1 week ago", "2025-03-03"),
+            ("2 months ago", "2025-01-09"),
+            ("1 year ago", "2024-03-10"),
+        ];
+        for (text, expected) in cases {
+            assert_eq!(
+                parse_date_text_from(text, now()),
+                day(expected),
+                "{:?}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn relative_dates_are_stable_within_a_day() {
+        let later = now() + Duration::hours(8);
+        assert_eq!(
+            parse_date_text_from("3 days ago", now()),
+            parse_date_text_from("3 days ago", later)
+        );
+    }
+
+    #[test]
+    fn rejects_unrecognised_text() {
+        for text in [
+            "",
+            "   ",
+            "soon",
+            "5 fortnights ago",
+            "2 days from now",
+            "Jan 45, 2025",
+        ] {
+            assert_eq!(parse_date_text_from(text, now()), None, "{:?}", text);
+        }
+    }
+}
@@ -0,0 +1,273 @@
+use reqwest::Url;
+use scraper::{Html, Selector};
+use serde_json::Value;
+
+/// JSON-LD `@type`s treated as the page's article node.
+const ARTICLE_TYPES: &[&str] = &[
+    "Article",
+    "NewsArticle",
+    "BlogPosting",
+    "TechArticle",
+    "ReportageNewsArticle",
+];
+
+/// Article fields published by the page itself, from JSON-LD or Open Graph tags.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct StructuredMetadata {
+    pub title: Option<String>,
+    pub author: Option<String>,
+    pub published_date: Option<String>,
+    pub image: Option<String>,
+}
+
+/// Read JSON-LD `Article`/`NewsArticle` data and Open Graph meta tags. JSON-LD wins
+/// when both are present; each field falls back independently.
+pub fn extract_structured_metadata(document: &Html) -> StructuredMetadata {
+    let json_ld = extract_json_ld(document);
+    let og = extract_open_graph(document);
+
+    StructuredMetadata {
+        title: json_ld.title.or(og.title),
+        author: json_ld.author.or(og.author),
+        published_date: json_ld.published_date.or(og.published_date),
+        image: json_ld.image.or(og.image),
+    }
+}
+
+fn extract_json_ld(document: &Html) -> StructuredMetadata {
+    let script_sel = Selector::parse(r#"script[type="application/ld+json"]"#).unwrap();
+
+    for script in document.select(&script_sel) {
+        let raw = script.text().collect::<String>();
+        let Ok(value) = serde_json::from_str::<Value>(&raw) else {
+            continue;
+        };
+        if let Some(node) = find_article_node(&value) {
+            return StructuredMetadata {
+                title: string_field(node, "headline").or_else(|| string_field(node, "name")),
+                author: node.get("author").and_then(person_name),
+                published_date: string_field(node, "datePublished"),
+                image: node.get("image").and_then(image_url),
+            };
+        }
+    }
+
+    StructuredMetadata::default()
+}
+
+fn find_article_node(value: &Value) -> Option<&Value> {
+    match value {
+        Value::Array(items) => items.iter().find_map(find_article_node),
+        Value::Object(map) => {
+            if is_article_type(map.get("@type")) {
+                return Some(value);
+            }
+            map.get("@graph").and_then(find_article_node)
+        }
+        _ => None,
+    }
+}
+
+fn is_article_type(ty: Option<&Value>) -> bool {
+    match ty {
+        Some(Value::String(s)) => ARTICLE_TYPES.contains(&s.as_str()),
+        Some(Value::Array(types)) => types
+            .iter()
+            .any(|t| t.as_str().is_some_and(|s| ARTICLE_TYPES.contains(&s))),
+        _ => false,
+    }
+}
+
+fn string_field(node: &Value, key: &str) -> Option<String> {
+    node.get(key)
+        .and_then(Value::as_str)
+        .map(|s| s.trim().to_string())
+        .filter(|s| !s.is_empty())
+}
+
+fn person_name(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+        Value::Object(_) => string_field(value, "name"),
+        Value::Array(people) => {
+            let names: Vec<String> = people.iter().filter_map(person_name).collect();
+            (!names.is_empty()).then(|| names.join(", "))
+        }
+        _ => None,
+    }
+}
+
+fn image_url(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.trim().to_string()).filter(|s| !s.is_empty()),
+        Value::Object(_) => string_field(value, "url"),
+        Value::Array(images) => images.iter().find_map(image_url),
+        _ => None,
+    }
+}
+
+fn extract_open_graph(document: &Html) -> StructuredMetadata {
+    StructuredMetadata {
+        title: meta_content(document, r#"meta[property="og:title"]"#),
+        author: meta_content(document, r#"meta[name="author"]"#),
+        published_date: meta_content(document, r#"meta[property="article:published_time"]"#),
+        image: meta_content(document, r#"meta[property="og:image"]"#),
+    }
+}
+
+fn meta_content(document: &Html, selector: &str) -> Option<String> {
+    let sel = Selector::parse(selector).unwrap();
+    document
+        .select(&sel)
+        .filter_map(|m| m.value().attr("content"))
+        .map(|s| s.trim().to_string())
+        .find(|s| !s.is_empty())
+}
+
+/// The page's `<link rel="canonical">` (or `og:url`) resolved against `fallback`, or
+/// `fallback` itself when the page declares none.
+pub fn canonical_url(document: &Html, fallback: &str) -> String {
+    let link_sel = Selector::parse(r#"link[rel="canonical"][href]"#).unwrap();
+    let declared = document
+        .select(&link_sel)
+        .filter_map(|l| l.value().attr("href"))
+        .map(|s| s.trim().to_string())
+        .find(|s| !s.is_empty())
+        .or_else(|| meta_content(document, r#"meta[property="og:url"]"#));
+
+    declared
+        .and_then(|href| match Url::parse(fallback) {
+            Ok(base) => base.join(&href).ok().map(|u| u.to_string()),
+            Err(_) => Url::parse(&href).ok().map(|u| u.to_string()),
+        })
+        .unwrap_or_else(|| fallback.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn page(head: &str) -> Html {
+        Html::parse_document(&format!("<html><head>{}</head><body></body></html>", head))
+    }
+
+    fn json_ld(value: serde_json::Value) -> String {
+        format!(r#"<script type="application/ld+json">{}</script>"#, value)
+    }
+
+    #[test]
+    fn reads_article_node_from_graph() {
+        let document = page(&json_ld(serde_json::json!({
+            "@context": "https://schema.org",
+            "@graph": [
+                { "@type": "WebSite", "name": "Example" },
+                {
+                    "@type": "BlogPosting",
+                    "headline": " Launch day ",
+                    "datePublished": "2025-01-05",
+                    "author": { "@type": "Person", "name": "Ada Lovelace" },
+                    "image": { "@type": "ImageObject", "url": "https://example.com/hero.png" }
+                }
+            ]
+        })));
+
+        assert_eq!(
+            extract_structured_metadata(&document),
+            StructuredMetadata {
+                title: Some("Launch day".to_string()),
+                author: Some("Ada Lovelace".to_string()),
+                published_date: Some("2025-01-05".to_string()),
+                image: Some("https://example.com/hero.png".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn accepts_array_type_and_author_list() {
+        let document = page(&json_ld(serde_json::json!([{
+            "@type": ["NewsArticle", "Thing"],
+            "name": "Fallback name",
+            "author": [{ "name": "Ada" }, "Grace", { "url": "https://example.com" }],
+            "image": ["https://example.com/a.png", "https://example.com/b.png"]
+        }])));
+
+        let metadata = extract_structured_metadata(&document);
+
+        assert_eq!(metadata.title.as_deref(), Some("Fallback name"));
+        assert_eq!(metadata.author.as_deref(), Some("Ada, Grace"));
+        assert_eq!(metadata.image.as_deref(), Some("https://example.com/a.png"));
+    }
+
+    #[test]
+    fn falls_back_to_open_graph_per_field() {
+        let document = page(&format!(
+            r#"{}
+            <meta property="og:title" content="OG title">
+            <meta name="author" content="OG author">
+            <meta property="article:published_time" content="2025-02-01T10:00:00Z">
+            <meta property="og:image" content="https://example.com/og.png">"#,
+            json_ld(serde_json::json!({ "@type": "Article", "headline": "LD title" }))
+        ));
+
+        assert_eq!(
+            extract_structured_metadata(&document),
+            StructuredMetadata {
+                title: Some("LD title".to_string()),
+                author: Some("OG author".to_string()),
+                published_date: Some("2025-02-01T10:00:00Z".to_string()),
+                image: Some("https://example.com/og.png".to_string()),
+            }
+        );
+    }
+
+    #[test]
+    fn ignores_invalid_json_and_non_article_types() {
+        let document = page(&format!(
+            r#"<script type="application/ld+json">{{ not json</script>{}"#,
+            json_ld(serde_json::json!({ "@type": "Organization", "name": "Example" }))
+        ));
+
+        assert_eq!(
+            extract_structured_metadata(&document),
+            StructuredMetadata::default()
+        );
+    }
+
+    #[test]
+    fn canonical_link_overrides_fetched_url() {
+        let fetched = "https://openai.com/news/research/copy/?utm_source=feed";
+
+        assert_eq!(
+            canonical_url(
+                &page(r#"<link rel="canonical" href="/index/original/">"#),
+                fetched
+            ),
+            "https://openai.com/index/original/"
+        );
+        assert_eq!(
+            canonical_url(
+                &page(r#"<link rel="canonical" href="https://example.com/original">"#),
+                fetched
+            ),
+            "https://example.com/original"
+        );
+    }
+
+    #[test]
+    fn canonical_falls_back_to_og_url_then_fetched_url() {
+        let fetched = "https://openai.com/news/research/copy/";
+
+        assert_eq!(
+            canonical_url(
+                &page(r#"<meta property="og:url" content="https://openai.com/index/og/">"#),
+                fetched
+            ),
+            "https://openai.com/index/og/"
+        );
+        assert_eq!(
+            canonical_url(&page(r#"<link rel="canonical" href="  ">"#), fetched),
+            fetched
+        );
+        assert_eq!(canonical_url(&page(""), fetched), fetched);
+    }
+}
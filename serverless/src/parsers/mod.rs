@@ -1,20 +1,29 @@
 use anyhow::{Context, Result};
 use async_trait::async_trait;
-use reqwest::Client;
+use reqwest::{Client, Url};
 use scraper::{ElementRef, Html, Selector};
 use tracing::{info, warn};
 
-use crate::models::{ListingItem, ScrapedArticle};
+use crate::http::read_html;
+use crate::models::{Listing, ListingItem, ListingOutcome, ScrapedArticle};
+use crate::parsers::dates::parse_date_text;
+use crate::parsers::metadata::{canonical_url, extract_structured_metadata};
+use crate::parsers::selectors::{news_list_selectors, NewsListSelectors};
+use crate::sanitize::sanitize_html;
 use serde::Serialize;
 
+pub mod config_parser;
+pub mod dates;
+pub mod metadata;
 pub mod openai_company_announcements;
 pub mod openai_engineering;
 pub mod openai_product_releases;
 pub mod openai_research;
 pub mod openai_safety_alignment;
 pub mod openai_security;
+pub mod selectors;
 
-pub(crate) const OPENAI_BASE: &str = "https://openai.com";
+pub const OPENAI_BASE: &str = "https://openai.com";
 pub(crate) const OPENAI_PRODUCT_RELEASES_LISTING: &str =
     "https://openai.com/news/product-releases/?display=list";
 pub(crate) const OPENAI_SECURITY_LISTING: &str = "https://openai.com/news/security/?display=list";
@@ -36,40 +45,71 @@ pub struct Article {
     pub url: String,
 }
 
+/// A site parser. Selector logic lives in the `*_from_html` methods so it can run
+/// against saved HTML; `parse_listing`/`parse_article` just fetch and delegate.
 #[async_trait]
 pub trait Parser: Send + Sync {
     fn name(&self) -> &str;
-    async fn parse_listing(&self) -> Result<Vec<ListingItem>>;
-    async fn parse_article(&self, url: &str) -> Result<ScrapedArticle>;
+    fn listing_url(&self) -> &str;
+    fn client(&self) -> &Client;
+    fn parse_listing_from_html(&self, html: &str) -> Listing;
+    fn parse_article_from_html(&self, html: &str, url: &str) -> Result<ScrapedArticle>;
+
+    async fn parse_listing(&self) -> Result<Listing> {
+        let html = fetch_html(self.client(), self.listing_url()).await?;
+        Ok(self.parse_listing_from_html(&html))
+    }
+
+    async fn parse_article(&self, url: &str) -> Result<ScrapedArticle> {
+        let html = fetch_html(self.client(), url).await?;
+        self.parse_article_from_html(&html, url)
+    }
 }
 
-/// Generic parser for OpenAI news list pages (e.g. /news/product-releases/?display=list).
+/// GET `url` and read it as HTML (content-type checked, size capped).
+pub(crate) async fn fetch_html(client: &Client, url: &str) -> Result<String> {
+    read_html(client.get(url).send().await?).await
+}
+
+/// Generic parser for OpenAI news list pages (e.g. /news/product-releases/?display=list),
+/// using the selectors configured via `OPENAI_LISTING_SELECTORS`.
 pub fn parse_openai_news_list(html: &str, base_url: &str) -> Vec<Article> {
+    parse_openai_news_list_with(html, base_url, news_list_selectors()).0
+}
+
+/// `parse_openai_news_list` with an explicit selector set, also reporting whether
+/// the row selector matched at all.
+pub fn parse_openai_news_list_with(
+    html: &str,
+    base_url: &str,
+    selectors: &NewsListSelectors,
+) -> (Vec<Article>, ListingOutcome) {
     let document = Html::parse_document(html);
+    let sel = selectors.compile();
 
-    let rows_sel = Selector::parse("div.grid > div.py-md").unwrap();
-    let meta_sel = Selector::parse("div.text-meta").unwrap();
-    let title_sel = Selector::parse(".text-h5").unwrap();
-    let summary_sel = Selector::parse("p.text-p2").unwrap();
-    let time_sel = Selector::parse("time").unwrap();
-    let link_sel = Selector::parse("a[href]").unwrap();
-    let first_div_sel = Selector::parse("div").unwrap();
+    if document.select(&sel.row).next().is_none() {
+        warn!(
+            "TAG:SELECTOR_MISS parser=openai_news_list selector={:?} base_url={}",
+            selectors.row, base_url
+        );
+        return (Vec::new(), ListingOutcome::SelectorMiss);
+    }
 
     let mut out = Vec::new();
 
-    for row in document.select(&rows_sel) {
-        let Some(meta) = row.select(&meta_sel).next() else {
+    for row in document.select(&sel.row) {
+        let Some(meta) = row.select(&sel.meta).next() else {
             continue;
         };
 
         let category = meta
-            .select(&first_div_sel)
+            .select(&sel.category)
             .next()
             .map(|d| d.text().collect::<String>())
             .map(|s| s.trim().to_string())
             .unwrap_or_default();
 
-        let time_el = meta.select(&time_sel).next();
+        let time_el = meta.select(&sel.time).next();
         let date_text = time_el
             .as_ref()
             .map(|t| t.text().collect::<String>())
@@ -77,23 +117,20 @@ pub fn parse_openai_news_list(html: &str, base_url: &str) -> Vec<Article> {
             .unwrap_or_default();
         let date_iso = time_el
             .and_then(|t| t.value().attr("datetime"))
-            .map(|s| s.to_string());
+            .map(|s| s.to_string())
+            .or_else(|| parse_date_text(&date_text).map(|d| d.to_rfc3339()));
 
-        let Some(link) = row.select(&link_sel).next() else {
+        let Some(link) = row.select(&sel.link).next() else {
             continue;
         };
         let Some(href) = link.value().attr("href") else {
             continue;
         };
 
-        let url = if href.starts_with("http://") || href.starts_with("https://") {
-            href.to_string()
-        } else {
-            format!("{}{}", base_url.trim_end_matches('/'), href)
-        };
+        let url = absolute_url(base_url, href);
 
         let title = link
-            .select(&title_sel)
+            .select(&sel.title)
             .next()
             .map(|h| h.text().collect::<String>())
             .map(|s| s.trim().to_string())
@@ -103,7 +140,7 @@ pub fn parse_openai_news_list(html: &str, base_url: &str) -> Vec<Article> {
         }
 
         let summary = link
-            .select(&summary_sel)
+            .select(&sel.summary)
             .next()
             .map(|p| p.text().collect::<String>())
             .map(|s| s.trim().to_string())
@@ -119,22 +156,24 @@ pub fn parse_openai_news_list(html: &str, base_url: &str) -> Vec<Article> {
         });
     }
 
-    out
+    let outcome = ListingOutcome::classify(out.len(), true);
+    (out, outcome)
 }
 
-/// Fetch and parse an OpenAI news listing page, logging structured output.
-pub(crate) async fn fetch_openai_news_listing(
-    client: &Client,
-    listing_url: &str,
+/// Parse an OpenAI news listing page into listing items, logging structured output.
+pub(crate) fn openai_listing_from_html(
+    html: &str,
     parser_name: &str,
-) -> Result<Vec<ListingItem>> {
-    let html = client.get(listing_url).send().await?.text().await?;
-    let articles = parse_openai_news_list(&html, OPENAI_BASE);
+    listing_url: &str,
+) -> Listing {
+    let (articles, outcome) = parse_openai_news_list_with(html, OPENAI_BASE, news_list_selectors());
 
     if articles.is_empty() {
         warn!(
-            "TAG:OPENAI_LISTING_EMPTY parser={} url={}",
-            parser_name, listing_url
+            "TAG:OPENAI_LISTING_EMPTY parser={} url={} outcome={}",
+            parser_name,
+            listing_url,
+            outcome.as_str()
         );
     }
 
@@ -150,62 +189,63 @@ pub(crate) async fn fetch_openai_news_listing(
         );
     }
 
-    Ok(articles
+    let items = articles
         .into_iter()
         .map(|a| ListingItem {
             url: a.url,
             title: a.title,
             category: a.category,
             date_text: a.date_text,
+            date_iso: a.date_iso,
         })
-        .collect())
-}
+        .collect();
 
-pub(crate) async fn parse_openai_article(
-    client: &Client,
-    url: &str,
-    _parser_name: &str,
-) -> Result<ScrapedArticle> {
-    let html = client.get(url).send().await?.text().await?;
-    parse_openai_article_html(&html)
+    Listing { items, outcome }
 }
 
-pub(crate) fn parse_openai_article_html(html: &str) -> Result<ScrapedArticle> {
+pub(crate) fn parse_openai_article_html(html: &str, url: &str) -> Result<ScrapedArticle> {
     let document = Html::parse_document(html);
+    let structured = extract_structured_metadata(&document);
 
     let title_selector = Selector::parse("h1").unwrap();
-    let title = document
-        .select(&title_selector)
-        .next()
-        .map(|t| t.text().collect::<String>())
-        .unwrap_or_else(|| "Untitled".to_string());
+    let title = structured.title.unwrap_or_else(|| {
+        document
+            .select(&title_selector)
+            .next()
+            .map(|t| t.text().collect::<String>())
+            .unwrap_or_else(|| "Untitled".to_string())
+    });
 
     let date_selector = Selector::parse("time").unwrap();
-    let published_date = document
-        .select(&date_selector)
-        .next()
-        .and_then(|d| d.value().attr("datetime"))
-        .unwrap_or("Unknown")
-        .to_string();
+    let published_date = structured.published_date.unwrap_or_else(|| {
+        document
+            .select(&date_selector)
+            .next()
+            .and_then(|d| d.value().attr("datetime"))
+            .unwrap_or("Unknown")
+            .to_string()
+    });
 
     let content_selector = Selector::parse("article, main").unwrap();
     let content_element = document
         .select(&content_selector)
         .next()
         .context("No content found")?;
-    let content_html = content_element.html();
+    let content_html = sanitize_html(&content_element.html());
     let content_text = extract_text(&content_element);
 
     let images_selector = Selector::parse("article img").unwrap();
-    let images: Vec<String> = content_element
+    let mut images: Vec<String> = content_element
         .select(&images_selector)
         .filter_map(|img| img.value().attr("src"))
         .map(|src| absolute_url(OPENAI_BASE, src))
         .collect();
+    prepend_hero_image(&mut images, structured.image, OPENAI_BASE);
 
     let article = ScrapedArticle {
+        canonical_url: canonical_url(&document, url),
         title: title.trim().to_string(),
-        author: "OpenAI".to_string(),
+        author: structured.author.unwrap_or_else(|| "OpenAI".to_string()),
         published_date,
         content_html,
         content_text,
@@ -215,24 +255,79 @@ pub(crate) fn parse_openai_article_html(html: &str) -> Result<ScrapedArticle> {
     Ok(article)
 }
 
+/// Put the page's declared hero image (og:image / JSON-LD image) first, without duplicating it.
+pub(crate) fn prepend_hero_image(images: &mut Vec<String>, hero: Option<String>, base: &str) {
+    if let Some(hero) = hero.map(|src| absolute_url(base, &src)) {
+        images.retain(|img| img != &hero);
+        images.insert(0, hero);
+    }
+}
+
+/// Resolve `url` (absolute, `//host`, `/path`, `path`, `?query` or `#fragment`) against
+/// `base` per RFC 3986. Never panics: unresolvable input is returned unchanged.
 pub(crate) fn absolute_url(base: &str, url: &str) -> String {
-    if url.starts_with("http") {
-        url.to_string()
-    } else if url.starts_with("//") {
-        format!("https:{}", url)
-    } else if url.starts_with('/') {
-        format!("{}{}", base.trim_end_matches('/'), url)
-    } else {
-        format!("{}/{}", base.trim_end_matches('/'), url)
+    let url = url.trim();
+    if url.is_empty() || Url::parse(url).is_ok() {
+        return url.to_string();
     }
+    Url::parse(base)
+        .and_then(|base| base.join(url))
+        .map(|resolved| resolved.to_string())
+        .unwrap_or_else(|_| url.to_string())
 }
 
+/// Elements whose text is code or fallback markup rather than article prose.
+const NON_PROSE_TAGS: &[&str] = &["script", "style", "noscript", "template"];
+
+/// Visible text of `element` with whitespace collapsed; script/style contents are skipped.
 pub(crate) fn extract_text(element: &ElementRef<'_>) -> String {
     element
-        .text()
+        .descendants()
+        .filter_map(|node| node.value().as_text().map(|text| (node, text)))
+        .filter(|(node, _)| {
+            !node.ancestors().any(|a| {
+                a.value()
+                    .as_element()
+                    .is_some_and(|el| NON_PROSE_TAGS.contains(&el.name()))
+            })
+        })
+        .map(|(_, text)| &**text)
         .collect::<Vec<_>>()
         .join(" ")
         .split_whitespace()
         .collect::<Vec<_>>()
         .join(" ")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::absolute_url;
+
+    #[test]
+    fn absolute_url_resolves_every_reference_form() {
+        let base = "https://openai.com/news/";
+        let cases = [
+            ("https://example.com/a", "https://example.com/a"),
+            ("http://example.com/a?b=1", "http://example.com/a?b=1"),
+            (
+                "//cdn.example.com/img.png",
+                "https://cdn.example.com/img.png",
+            ),
+            ("/index/post/", "https://openai.com/index/post/"),
+            ("post/", "https://openai.com/news/post/"),
+            ("?page=2", "https://openai.com/news/?page=2"),
+            ("#section", "https://openai.com/news/#section"),
+            ("  /padded  ", "https://openai.com/padded"),
+            ("", ""),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(absolute_url(base, input), expected, "input {:?}", input);
+        }
+    }
+
+    #[test]
+    fn absolute_url_returns_input_for_bad_base() {
+        assert_eq!(absolute_url("not a url", "/path"), "/path");
+        assert_eq!(absolute_url("", "post"), "post");
+    }
+}
@@ -1,10 +1,10 @@
 use anyhow::Result;
-use async_trait::async_trait;
 use reqwest::Client;
 
-use crate::models::{ListingItem, ScrapedArticle};
+use crate::http::build_http_client;
+use crate::models::{Listing, ScrapedArticle};
 use crate::parsers::{
-    fetch_openai_news_listing, parse_openai_article, Parser, OPENAI_ENGINEERING_LISTING,
+    openai_listing_from_html, parse_openai_article_html, Parser, OPENAI_ENGINEERING_LISTING,
 };
 
 pub struct OpenAIEngineeringParser {
@@ -14,25 +14,35 @@ pub struct OpenAIEngineeringParser {
 impl OpenAIEngineeringParser {
     pub fn new() -> Self {
         Self {
-            client: Client::builder()
-                .user_agent("Mozilla/5.0 (compatible; BlogScraper/1.0)")
-                .build()
-                .expect("failed to build reqwest client"),
+            client: build_http_client().expect("failed to build reqwest client"),
         }
     }
 }
 
-#[async_trait]
+impl Default for OpenAIEngineeringParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Parser for OpenAIEngineeringParser {
     fn name(&self) -> &str {
         "openai-engineering"
     }
 
-    async fn parse_listing(&self) -> Result<Vec<ListingItem>> {
-        fetch_openai_news_listing(&self.client, OPENAI_ENGINEERING_LISTING, self.name()).await
+    fn listing_url(&self) -> &str {
+        OPENAI_ENGINEERING_LISTING
+    }
+
+    fn client(&self) -> &Client {
+        &self.client
+    }
+
+    fn parse_listing_from_html(&self, html: &str) -> Listing {
+        openai_listing_from_html(html, self.name(), self.listing_url())
     }
 
-    async fn parse_article(&self, url: &str) -> Result<ScrapedArticle> {
-        parse_openai_article(&self.client, url, self.name()).await
+    fn parse_article_from_html(&self, html: &str, url: &str) -> Result<ScrapedArticle> {
+        parse_openai_article_html(html, url)
     }
 }
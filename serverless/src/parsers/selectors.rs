@@ -0,0 +1,95 @@
+use std::sync::OnceLock;
+
+use scraper::Selector;
+use serde::Deserialize;
+use tracing::warn;
+
+/// CSS selectors used by `parse_openai_news_list`. Overridable as a JSON object in
+/// `OPENAI_LISTING_SELECTORS`; any field left out keeps its default.
+#[derive(Debug, Clone, Deserialize, PartialEq)]
+#[serde(default)]
+pub struct NewsListSelectors {
+    /// One element per listing entry.
+    pub row: String,
+    /// Block inside a row holding the category and `<time>`.
+    pub meta: String,
+    /// First match inside `meta` is the category label.
+    pub category: String,
+    pub time: String,
+    pub link: String,
+    /// Evaluated inside the row's link.
+    pub title: String,
+    /// Evaluated inside the row's link.
+    pub summary: String,
+}
+
+impl Default for NewsListSelectors {
+    fn default() -> Self {
+        Self {
+            row: "div.grid > div.py-md".to_string(),
+            meta: "div.text-meta".to_string(),
+            category: "div".to_string(),
+            time: "time".to_string(),
+            link: "a[href]".to_string(),
+            title: ".text-h5".to_string(),
+            summary: "p.text-p2".to_string(),
+        }
+    }
+}
+
+impl NewsListSelectors {
+    /// Selectors from `OPENAI_LISTING_SELECTORS`, or the defaults when it is unset or
+    /// not valid JSON.
+    pub fn from_env() -> Self {
+        let Ok(raw) = std::env::var("OPENAI_LISTING_SELECTORS") else {
+            return Self::default();
+        };
+
+        serde_json::from_str(&raw).unwrap_or_else(|e| {
+            warn!(
+                "Invalid OPENAI_LISTING_SELECTORS ({}); using default selectors",
+                e
+            );
+            Self::default()
+        })
+    }
+
+    pub(crate) fn compile(&self) -> CompiledNewsListSelectors {
+        let defaults = Self::default();
+        CompiledNewsListSelectors {
+            row: compile_or_default("row", &self.row, &defaults.row),
+            meta: compile_or_default("meta", &self.meta, &defaults.meta),
+            category: compile_or_default("category", &self.category, &defaults.category),
+            time: compile_or_default("time", &self.time, &defaults.time),
+            link: compile_or_default("link", &self.link, &defaults.link),
+            title: compile_or_default("title", &self.title, &defaults.title),
+            summary: compile_or_default("summary", &self.summary, &defaults.summary),
+        }
+    }
+}
+
+pub(crate) struct CompiledNewsListSelectors {
+    pub row: Selector,
+    pub meta: Selector,
+    pub category: Selector,
+    pub time: Selector,
+    pub link: Selector,
+    pub title: Selector,
+    pub summary: Selector,
+}
+
+/// Process-wide selectors, read from the environment once per Lambda container.
+pub fn news_list_selectors() -> &'static NewsListSelectors {
+    static SELECTORS: OnceLock<NewsListSelectors> = OnceLock::new();
+    SELECTORS.get_or_init(NewsListSelectors::from_env)
+}
+
+fn compile_or_default(field: &str, selector: &str, default: &str) -> Selector {
+    Selector::parse(selector).unwrap_or_else(|e| {
+        warn!(
+            "Invalid OPENAI_LISTING_SELECTORS.{} {:?} ({}); using {:?}",
+            field, selector, e, default
+        );
+        Selector::parse(default).unwrap()
+    })
+}
@@ -0,0 +1,241 @@
+use scraper::{ElementRef, Html};
+
+/// Elements dropped together with everything inside them.
+const DROPPED_TAGS: &[&str] = &[
+    "script", "style", "iframe", "frame", "frameset", "object", "embed", "applet", "noscript",
+    "template", "form", "input", "button", "select", "textarea", "option", "link", "meta", "base",
+    "svg", "math", "canvas",
+];
+
+/// Elements kept as-is (with filtered attributes). Anything not listed here and
+/// not dropped is unwrapped: its children are kept but the tag itself is not.
+const ALLOWED_TAGS: &[&str] = &[
+    "a",
+    "abbr",
+    "article",
+    "b",
+    "blockquote",
+    "br",
+    "caption",
+    "cite",
+    "code",
+    "dd",
+    "del",
+    "dfn",
+    "div",
+    "dl",
+    "dt",
+    "em",
+    "figcaption",
+    "figure",
+    "h1",
+    "h2",
+    "h3",
+    "h4",
+    "h5",
+    "h6",
+    "hr",
+    "i",
+    "img",
+    "ins",
+    "kbd",
+    "li",
+    "main",
+    "mark",
+    "ol",
+    "p",
+    "picture",
+    "pre",
+    "q",
+    "s",
+    "samp",
+    "section",
+    "small",
+    "source",
+    "span",
+    "strong",
+    "sub",
+    "sup",
+    "table",
+    "tbody",
+    "td",
+    "tfoot",
+    "th",
+    "thead",
+    "time",
+    "tr",
+    "u",
+    "ul",
+];
+
+const VOID_TAGS: &[&str] = &["br", "hr", "img", "source"];
+
+/// Strip scripts, embeds, event handlers and unsafe URLs from scraped HTML while
+/// keeping headings, links, images and basic formatting.
+pub fn sanitize_html(html: &str) -> String {
+    let fragment = Html::parse_fragment(html);
+    let mut out = String::with_capacity(html.len());
+    write_children(&fragment.root_element(), &mut out);
+    out
+}
+
+fn write_children(element: &ElementRef<'_>, out: &mut String) {
+    for child in element.children() {
+        if let Some(text) = child.value().as_text() {
+            push_escaped(out, text);
+        } else if let Some(child_element) = ElementRef::wrap(child) {
+            write_element(&child_element, out);
+        }
+        // Comments, doctypes and processing instructions are dropped.
+    }
+}
+
+fn write_element(element: &ElementRef<'_>, out: &mut String) {
+    let name = element.value().name();
+
+    if DROPPED_TAGS.contains(&name) {
+        return;
+    }
+
+    if !ALLOWED_TAGS.contains(&name) {
+        write_children(element, out);
+        return;
+    }
+
+    out.push('<');
+    out.push_str(name);
+    for (attr, value) in element.value().attrs() {
+        if attribute_allowed(name, attr, value) {
+            out.push(' ');
+            out.push_str(attr);
+            out.push_str("=\"");
+            push_escaped(out, value);
+            out.push('"');
+        }
+    }
+    out.push('>');
+
+    if VOID_TAGS.contains(&name) {
+        return;
+    }
+
+    write_children(element, out);
+    out.push_str("</");
+    out.push_str(name);
+    out.push('>');
+}
+
+fn attribute_allowed(tag: &str, attr: &str, value: &str) -> bool {
+    match (tag, attr) {
+        (_, "title") => true,
+        ("a", "href") => is_safe_url(value, true),
+        ("img", "src") | ("blockquote", "cite") | ("q", "cite") => is_safe_url(value, false),
+        ("img", "srcset") | ("source", "srcset") => value
+            .split(',')
+            .filter_map(|candidate| candidate.split_whitespace().next())
+            .all(|url| is_safe_url(url, false)),
+        ("img", "alt" | "width" | "height" | "sizes") => true,
+        ("source", "type" | "media" | "sizes") => true,
+        ("time", "datetime") => true,
+        ("td" | "th", "colspan" | "rowspan") => true,
+        ("ol", "start") => true,
+        _ => false,
+    }
+}
+
+fn is_safe_url(value: &str, allow_mailto: bool) -> bool {
+    let value = value.trim();
+    let lower = value.to_ascii_lowercase();
+
+    match lower.find(':') {
+        // Relative URLs have no scheme, or only a colon after a path/query/fragment delimiter.
+        Some(idx) if !lower[..idx].contains(['/', '?', '#']) => {
+            let scheme = &lower[..idx];
+            scheme == "http" || scheme == "https" || (allow_mailto && scheme == "mailto")
+        }
+        _ => true,
+    }
+}
+
+fn push_escaped(out: &mut String, text: &str) {
+    for c in text.chars() {
+        match c {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            _ => out.push(c),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::sanitize_html;
+
+    #[test]
+    fn drops_scripts_and_embedded_documents() {
+        let html = concat!(
+            "<p>Intro</p>",
+            "<script>alert(1)</script>",
+            "<iframe src=\"https://evil.example\"></iframe>",
+            "<svg><script>alert(2)</script></svg>",
+            "<math><mi>x</mi></math>",
+            "<noscript><img src=\"https://track.example/p.gif\"></noscript>",
+            "<p>Outro</p>",
+        );
+
+        assert_eq!(sanitize_html(html), "<p>Intro</p><p>Outro</p>");
+    }
+
+    #[test]
+    fn strips_event_handlers() {
+        let out = sanitize_html(
+            "<img src=\"https://example.com/a.png\" onerror=\"alert(1)\" alt=\"A\">\
+             <p onclick=\"alert(2)\">Hi</p>",
+        );
+
+        assert_eq!(
+            out,
+            "<img alt=\"A\" src=\"https://example.com/a.png\"><p>Hi</p>"
+        );
+    }
+
+    #[test]
+    fn strips_javascript_urls() {
+        let payloads = [
+            "<a href=\"javascript:alert(1)\">x</a>",
+            "<a href=\"JaVaScRiPt:alert(1)\">x</a>",
+            "<a href=\"  javascript:alert(1)\">x</a>",
+            "<a href=\"java&#x09;script:alert(1)\">x</a>",
+        ];
+        for payload in payloads {
+            assert_eq!(sanitize_html(payload), "<a>x</a>", "payload {:?}", payload);
+        }
+    }
+
+    #[test]
+    fn strips_data_image_sources() {
+        assert_eq!(
+            sanitize_html("<img src=\"data:image/svg+xml;base64,PHN2Zz4=\" alt=\"x\">"),
+            "<img alt=\"x\">"
+        );
+    }
+
+    #[test]
+    fn keeps_headings_links_and_images() {
+        let html = "<h2>Title</h2><p>See <a href=\"https://openai.com/index/\">this</a> \
+                    and <a href=\"/relative\">that</a>.</p>\
+                    <img alt=\"Chart\" src=\"https://example.com/a.png\">";
+
+        assert_eq!(sanitize_html(html), html);
+    }
+
+    #[test]
+    fn unwraps_unknown_tags_and_escapes_text() {
+        assert_eq!(
+            sanitize_html("<custom-card><p>a &lt;b&gt; \"c\"</p></custom-card>"),
+            "<p>a &lt;b&gt; &quot;c&quot;</p>"
+        );
+    }
+}
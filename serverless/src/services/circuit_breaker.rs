@@ -0,0 +1,254 @@
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use reqwest::StatusCode;
+use tracing::{info, warn};
+
+use crate::http::FetchError;
+
+const DEFAULT_FAILURE_THRESHOLD: u32 = 3;
+const DEFAULT_COOLDOWN_SECS: u64 = 60;
+
+/// Whether `err` says the backend itself is unhealthy and should count toward opening
+/// its breaker: a transport failure or a 5xx/429 status, or a 401/403 (bad or expired
+/// credentials leave the backend just as unusable). Problems with the target page (a
+/// 404, an oversized or non-HTML body) mean the backend answered fine.
+pub fn counts_against_backend(err: &anyhow::Error) -> bool {
+    if err.downcast_ref::<FetchError>().is_some() {
+        return false;
+    }
+
+    match err
+        .chain()
+        .find_map(|cause| cause.downcast_ref::<reqwest::Error>())
+        .and_then(|e| e.status())
+    {
+        Some(status) => {
+            status.is_server_error()
+                || status == StatusCode::TOO_MANY_REQUESTS
+                || status == StatusCode::UNAUTHORIZED
+                || status == StatusCode::FORBIDDEN
+        }
+        None => true,
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BreakerState {
+    /// Requests flow normally.
+    Closed,
+    /// Too many consecutive failures; requests are skipped until the cooldown elapses.
+    Open,
+    /// Cooldown elapsed; one request at a time is let through as a trial that closes or
+    /// re-opens the breaker.
+    HalfOpen,
+}
+
+#[derive(Debug)]
+struct Inner {
+    consecutive_failures: u32,
+    opened_at: Option<Instant>,
+    /// When the in-flight half-open trial started, if one is running.
+    probe_started: Option<Instant>,
+}
+
+/// Consecutive-failure circuit breaker for a single crawler backend.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    name: String,
+    failure_threshold: u32,
+    cooldown: Duration,
+    inner: Mutex<Inner>,
+}
+
+impl CircuitBreaker {
+    pub fn new(name: &str, failure_threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            name: name.to_string(),
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            inner: Mutex::new(Inner {
+                consecutive_failures: 0,
+                opened_at: None,
+                probe_started: None,
+            }),
+        }
+    }
+
+    /// Breaker configured from `CIRCUIT_BREAKER_THRESHOLD` (failures before opening,
+    /// default 3) and `CIRCUIT_BREAKER_COOLDOWN_SECS` (default 60).
+    pub fn from_env(name: &str) -> Self {
+        let threshold = std::env::var("CIRCUIT_BREAKER_THRESHOLD")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_FAILURE_THRESHOLD);
+        let cooldown = std::env::var("CIRCUIT_BREAKER_COOLDOWN_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_COOLDOWN_SECS);
+
+        Self::new(name, threshold, Duration::from_secs(cooldown))
+    }
+
+    pub fn state(&self) -> BreakerState {
+        let inner = self.inner.lock().unwrap();
+        match inner.opened_at {
+            None => BreakerState::Closed,
+            Some(opened_at) if opened_at.elapsed() >= self.cooldown => BreakerState::HalfOpen,
+            Some(_) => BreakerState::Open,
+        }
+    }
+
+    /// Whether a request should be attempted against this backend right now. While
+    /// half-open only the first caller gets through; the rest are refused until its
+    /// result is recorded (or it has run for a full cooldown without reporting back).
+    pub fn allow_request(&self) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        let Some(opened_at) = inner.opened_at else {
+            return true;
+        };
+        if opened_at.elapsed() < self.cooldown {
+            return false;
+        }
+        if inner
+            .probe_started
+            .is_some_and(|started| started.elapsed() < self.cooldown)
+        {
+            return false;
+        }
+
+        info!("TAG:CIRCUIT_PROBE backend={}", self.name);
+        inner.probe_started = Some(Instant::now());
+        true
+    }
+
+    pub fn record_success(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.opened_at.is_some() {
+            info!("TAG:CIRCUIT_CLOSED backend={}", self.name);
+        }
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+        inner.probe_started = None;
+    }
+
+    /// Record a request outcome, counting only errors that `counts_against_backend`.
+    pub fn record_result<T>(&self, result: &anyhow::Result<T>) {
+        match result {
+            Err(e) if counts_against_backend(e) => self.record_failure(),
+            _ => self.record_success(),
+        }
+    }
+
+    pub fn record_failure(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.consecutive_failures += 1;
+        inner.probe_started = None;
+
+        // A failed half-open trial re-opens immediately; otherwise open on reaching the threshold.
+        if inner.opened_at.is_some() || inner.consecutive_failures >= self.failure_threshold {
+            warn!(
+                "TAG:CIRCUIT_OPEN backend={} consecutive_failures={} cooldown_secs={}",
+                self.name,
+                inner.consecutive_failures,
+                self.cooldown.as_secs()
+            );
+            inner.opened_at = Some(Instant::now());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn opens_after_threshold_then_half_opens_after_cooldown() {
+        let breaker = CircuitBreaker::new("test", 3, Duration::from_millis(50));
+
+        breaker.record_failure();
+        breaker.record_failure();
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        breaker.record_failure();
+        assert_eq!(breaker.state(), BreakerState::Open);
+        assert!(!breaker.allow_request());
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert_eq!(breaker.state(), BreakerState::HalfOpen);
+        assert!(breaker.allow_request());
+
+        // A failed trial re-opens immediately; a successful one closes.
+        breaker.record_failure();
+        assert_eq!(breaker.state(), BreakerState::Open);
+        std::thread::sleep(Duration::from_millis(60));
+        breaker.record_success();
+        assert_eq!(breaker.state(), BreakerState::Closed);
+    }
+
+    #[test]
+    fn half_open_lets_one_trial_through_at_a_time() {
+        let breaker = CircuitBreaker::new("test", 1, Duration::from_millis(50));
+        breaker.record_failure();
+        std::thread::sleep(Duration::from_millis(60));
+
+        assert!(breaker.allow_request());
+        assert!(!breaker.allow_request());
+        assert!(!breaker.allow_request());
+
+        // A failed trial re-opens, and the next cooldown admits a single new trial.
+        breaker.record_failure();
+        assert!(!breaker.allow_request());
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(breaker.allow_request());
+        assert!(!breaker.allow_request());
+
+        breaker.record_success();
+        assert!(breaker.allow_request());
+        assert!(breaker.allow_request());
+    }
+
+    #[test]
+    fn abandoned_trial_is_replaced_after_a_cooldown() {
+        let breaker = CircuitBreaker::new("test", 1, Duration::from_millis(50));
+        breaker.record_failure();
+        std::thread::sleep(Duration::from_millis(60));
+
+        assert!(breaker.allow_request());
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(breaker.allow_request());
+    }
+
+    #[test]
+    fn success_resets_the_failure_count() {
+        let breaker = CircuitBreaker::new("test", 2, Duration::from_secs(60));
+
+        breaker.record_failure();
+        breaker.record_success();
+        breaker.record_failure();
+
+        assert_eq!(breaker.state(), BreakerState::Closed);
+    }
+
+    #[test]
+    fn target_page_errors_do_not_count_against_backend() {
+        let too_large: anyhow::Error = FetchError::BodyTooLarge {
+            url: "https://example.com".to_string(),
+            limit: 10,
+        }
+        .into();
+        let not_html = anyhow::Error::from(FetchError::UnexpectedContentType {
+            url: "https://example.com/a.pdf".to_string(),
+            content_type: "application/pdf".to_string(),
+        })
+        .context("failed to read body");
+
+        assert!(!counts_against_backend(&too_large));
+        assert!(!counts_against_backend(&not_html));
+
+        let breaker = CircuitBreaker::new("test", 1, Duration::from_secs(60));
+        breaker.record_result::<()>(&Err(not_html));
+        assert_eq!(breaker.state(), BreakerState::Closed);
+        breaker.record_result::<()>(&Err(anyhow::anyhow!("connection reset")));
+        assert_eq!(breaker.state(), BreakerState::Open);
+    }
+}
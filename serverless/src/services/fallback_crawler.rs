@@ -1,10 +1,15 @@
+use std::collections::HashSet;
+
 use anyhow::{anyhow, Context, Result};
 use reqwest::Client;
-use tracing::{debug, info, warn};
+use tracing::{debug, error, info, warn};
 
-use crate::models::{ScrapeResults, Site};
+use crate::config::Config;
+use crate::http::{build_http_client, is_unexpected_content_type, read_html};
+use crate::models::{ScrapeResults, Site, SiteResult};
 use crate::parsers::parse_openai_article_html;
-use crate::storage::Storage;
+use crate::services::{already_pending, flush_then_fail, snapshot_failed_parse};
+use crate::storage::{listing_ids, ContentUpload, Storage};
 
 /// Fallback crawler: consumes provided_listing entries (e.g., S3-hosted HTML)
 /// and parses articles without relying on a site-specific parser.
@@ -18,12 +23,11 @@ pub struct FallbackCrawlerService {
 impl FallbackCrawlerService {
     pub async fn new() -> Result<Self> {
         let storage = Storage::from_env().await?;
-        let client = Client::builder()
-            .build()
-            .context("failed to build reqwest client for fallback crawler")?;
+        let client =
+            build_http_client().context("failed to build reqwest client for fallback crawler")?;
         let aws_config = aws_config::load_defaults(aws_config::BehaviorVersion::latest()).await;
         let s3 = aws_sdk_s3::Client::new(&aws_config);
-        let snapshot_bucket = std::env::var("SNAPSHOT_BUCKET").ok();
+        let snapshot_bucket = Config::from_env()?.snapshot_bucket;
 
         Ok(Self {
             storage,
@@ -52,32 +56,52 @@ impl FallbackCrawlerService {
                 continue;
             }
 
-            let added = self.process_provided_listing(site).await?;
-            results.new_articles += added;
+            match self.process_provided_listing(site).await {
+                Ok(outcome) => results.record(&site.name, outcome),
+                Err(e) => {
+                    error!("{}: provided listing scrape failed: {:?}", site.name, e);
+                    results.record_error(&site.name, &e);
+                }
+            }
         }
 
         Ok(results)
     }
 
-    async fn process_provided_listing(&self, site: &Site) -> Result<usize> {
-        let mut processed = 0;
+    async fn process_provided_listing(&self, site: &Site) -> Result<SiteResult> {
+        let mut outcome = SiteResult::default();
         let items = if let Some(limit) = site.top_articles {
             site.provided_listing.iter().take(limit).collect::<Vec<_>>()
         } else {
             site.provided_listing.iter().collect::<Vec<_>>()
         };
 
-        for item in items {
-            if !site.force
-                && self
-                    .storage
-                    .article_exists(&site.name, &item.title, &item.category, &item.date_text)
-                    .await?
-            {
+        let ids: Vec<[String; 2]> = items
+            .iter()
+            .map(|item| {
+                listing_ids(
+                    &site.name,
+                    &item.title,
+                    &item.category,
+                    &item.date_text,
+                    &item.url,
+                )
+            })
+            .collect();
+        let existing = if site.force {
+            HashSet::new()
+        } else {
+            self.storage.articles_exist(&ids.concat()).await?
+        };
+
+        let mut pending = Vec::new();
+        for (item, ids) in items.iter().zip(&ids) {
+            if ids.iter().any(|id| existing.contains(id)) {
                 info!(
                     "{}: skipping existing provided article \"{}\" ({})",
                     site.name, item.title, item.url
                 );
+                outcome.skipped += 1;
                 continue;
             }
 
@@ -86,15 +110,40 @@ impl FallbackCrawlerService {
                 site.name, item.title, item.url
             );
 
-            let html = self
-                .fetch_body(&item.url)
-                .await
-                .with_context(|| format!("failed to fetch provided article: {}", item.url))?;
-
-            let article = parse_openai_article_html(&html)?;
-
-            self.storage
-                .save_article_content(
+            let html = match self.fetch_body(&item.url).await {
+                Ok(html) => html,
+                Err(e) if is_unexpected_content_type(&e) => {
+                    warn!(
+                        "{}: skipping non-HTML provided article {}: {}",
+                        site.name, item.url, e
+                    );
+                    outcome.skipped += 1;
+                    continue;
+                }
+                Err(e) => {
+                    let e = e.context(format!("failed to fetch provided article: {}", item.url));
+                    return flush_then_fail(&self.storage, &pending, e).await;
+                }
+            };
+
+            let parsed = parse_openai_article_html(&html, &item.url);
+            snapshot_failed_parse(&self.storage, &item.url, &html, &parsed).await;
+            let article = match parsed {
+                Ok(article) => article,
+                Err(e) => return flush_then_fail(&self.storage, &pending, e).await,
+            };
+
+            if already_pending(&pending, &article.canonical_url) {
+                info!(
+                    "TAG:DUPLICATE_URL url={} already queued this run; skipping {}",
+                    article.canonical_url, item.url
+                );
+                outcome.skipped += 1;
+                continue;
+            }
+            match self
+                .storage
+                .upload_article_content(
                     &site.name,
                     if item.title.is_empty() {
                         &article.title
@@ -103,17 +152,22 @@ impl FallbackCrawlerService {
                     },
                     &item.category,
                     &item.date_text,
-                    &item.url,
+                    &article.canonical_url,
                     &article.content_html,
                     &article.content_text,
                     &article.images,
                 )
-                .await?;
-
-            processed += 1;
+                .await
+            {
+                Ok(ContentUpload::Uploaded(record)) => pending.push(*record),
+                Ok(ContentUpload::Skipped(_)) => outcome.skipped += 1,
+                Err(e) => return flush_then_fail(&self.storage, &pending, e).await,
+            }
         }
 
-        Ok(processed)
+        outcome.new += self.storage.save_articles(&pending).await?;
+
+        Ok(outcome)
     }
 
     async fn fetch_body(&self, url: &str) -> Result<String> {
@@ -141,7 +195,7 @@ impl FallbackCrawlerService {
             .error_for_status()
             .context("fallback returned error status")?;
 
-        Ok(res.text().await.context("failed to read fallback body")?)
+        read_html(res).await.context("failed to read fallback body")
     }
 
     async fn fetch_s3(&self, bucket: &str, key: &str) -> Result<String> {
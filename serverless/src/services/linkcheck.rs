@@ -0,0 +1,158 @@
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use chrono::Utc;
+use futures::stream::{self, StreamExt};
+use reqwest::{redirect::Policy, Client, StatusCode};
+use serde::Serialize;
+use tracing::{info, warn};
+
+use crate::http::http_client_builder;
+use crate::storage::Storage;
+
+const DEFAULT_CONCURRENCY: usize = 8;
+const DEFAULT_TIMEOUT_SECS: u64 = 10;
+
+/// Health of an article's `source_url` as of the last link check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SourceStatus {
+    /// 2xx.
+    Ok,
+    /// 3xx; the article moved but may still be reachable.
+    Redirect,
+    /// 4xx/5xx, timeout or connection failure.
+    Dead,
+}
+
+impl SourceStatus {
+    pub fn from_status(status: StatusCode) -> Self {
+        if status.is_success() {
+            SourceStatus::Ok
+        } else if status.is_redirection() {
+            SourceStatus::Redirect
+        } else {
+            SourceStatus::Dead
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SourceStatus::Ok => "ok",
+            SourceStatus::Redirect => "redirect",
+            SourceStatus::Dead => "dead",
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct LinkCheckSummary {
+    pub checked: usize,
+    pub ok: usize,
+    pub redirect: usize,
+    pub dead: usize,
+    /// Checks whose result could not be written back.
+    pub failed_updates: usize,
+}
+
+/// Client for link checks: the shared scraper identity, a per-request timeout, and no
+/// redirect following so 3xx responses are reported as such.
+pub fn linkcheck_client(timeout: Duration) -> Result<Client> {
+    http_client_builder()
+        .redirect(Policy::none())
+        .timeout(timeout)
+        .build()
+        .context("failed to build reqwest client for link checks")
+}
+
+/// `HEAD` the URL (falling back to `GET` when the server rejects `HEAD`) and classify
+/// the response. Transport errors and timeouts count as dead.
+pub async fn check_source_url(client: &Client, url: &str) -> SourceStatus {
+    let response = match client.head(url).send().await {
+        Ok(res)
+            if res.status() == StatusCode::METHOD_NOT_ALLOWED
+                || res.status() == StatusCode::NOT_IMPLEMENTED =>
+        {
+            client.get(url).send().await
+        }
+        other => other,
+    };
+
+    match response {
+        Ok(res) => SourceStatus::from_status(res.status()),
+        Err(e) => {
+            warn!("TAG:LINKCHECK request failed url={}: {}", url, e);
+            SourceStatus::Dead
+        }
+    }
+}
+
+/// Checks every stored article's source URL and records `source_status` and
+/// `last_checked` on its metadata item.
+pub struct LinkCheckService {
+    storage: Storage,
+    client: Client,
+    concurrency: usize,
+}
+
+impl LinkCheckService {
+    /// Concurrency comes from `LINKCHECK_CONCURRENCY` (default 8) and the per-request
+    /// timeout from `LINKCHECK_TIMEOUT_SECS` (default 10).
+    pub async fn new() -> Result<Self> {
+        let storage = Storage::from_env().await?;
+        let timeout = std::env::var("LINKCHECK_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_TIMEOUT_SECS);
+        let concurrency = std::env::var("LINKCHECK_CONCURRENCY")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .filter(|&n| n > 0)
+            .unwrap_or(DEFAULT_CONCURRENCY);
+
+        Ok(Self {
+            storage,
+            client: linkcheck_client(Duration::from_secs(timeout))?,
+            concurrency,
+        })
+    }
+
+    pub async fn execute(&self) -> Result<LinkCheckSummary> {
+        let urls = self.storage.list_source_urls().await?;
+        let mut summary = LinkCheckSummary::default();
+
+        let mut checks = stream::iter(urls)
+            .map(|(id, url)| async move {
+                let status = check_source_url(&self.client, &url).await;
+                let saved = self
+                    .storage
+                    .set_source_status(&id, status.as_str(), Utc::now().timestamp())
+                    .await;
+                (id, url, status, saved)
+            })
+            .buffer_unordered(self.concurrency);
+
+        while let Some((id, url, status, saved)) = checks.next().await {
+            summary.checked += 1;
+            match status {
+                SourceStatus::Ok => summary.ok += 1,
+                SourceStatus::Redirect => summary.redirect += 1,
+                SourceStatus::Dead => {
+                    warn!("TAG:LINKCHECK_DEAD id={} url={}", id, url);
+                    summary.dead += 1;
+                }
+            }
+            if let Err(e) = saved {
+                warn!("TAG:LINKCHECK {:?}", e);
+                summary.failed_updates += 1;
+            }
+        }
+
+        info!(
+            "TAG:LINKCHECK completed checked={} ok={} redirect={} dead={} failed_updates={}",
+            summary.checked, summary.ok, summary.redirect, summary.dead, summary.failed_updates
+        );
+
+        Ok(summary)
+    }
+}
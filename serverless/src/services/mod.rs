@@ -1,4 +1,242 @@
+pub mod circuit_breaker;
 pub mod fallback_crawler;
+pub mod linkcheck;
 pub mod playwright_crawler;
 pub mod scrapedo_crawler;
 pub mod scraper;
+
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use tracing::{error, info, warn};
+
+use crate::models::{ListingItem, ScrapedArticle};
+use crate::storage::{ArticleMetadataRecord, Storage};
+
+/// Whether a parse produced nothing usable: an error, or an empty title/content.
+pub(crate) fn is_failed_parse(parsed: &Result<ScrapedArticle>) -> bool {
+    match parsed {
+        Ok(article) => {
+            let title = article.title.trim();
+            title.is_empty() || title == "Untitled" || article.content_text.trim().is_empty()
+        }
+        Err(_) => true,
+    }
+}
+
+/// Best-effort debug snapshot of `html` when `parsed` failed; never fails the scrape.
+pub(crate) async fn snapshot_failed_parse(
+    storage: &Storage,
+    url: &str,
+    html: &str,
+    parsed: &Result<ScrapedArticle>,
+) {
+    if !is_failed_parse(parsed) {
+        return;
+    }
+    if let Err(e) = storage.save_debug_snapshot(url, html).await {
+        warn!("Failed to save parse snapshot for {}: {:?}", url, e);
+    }
+}
+
+/// The first `limit` listing entries, cut at the first one published before
+/// `watermark`. Listings are newest-first, so everything after that point was
+/// already walked by an earlier run. Undated entries never stop the walk.
+pub(crate) fn take_until_watermark<'a>(
+    items: &'a [ListingItem],
+    limit: usize,
+    watermark: Option<DateTime<Utc>>,
+    parser_name: &str,
+) -> &'a [ListingItem] {
+    let window = &items[..limit.min(items.len())];
+    let Some(watermark) = watermark else {
+        return window;
+    };
+
+    match window
+        .iter()
+        .position(|item| item.published_at().is_some_and(|d| d < watermark))
+    {
+        Some(stop) => {
+            info!(
+                "TAG:WATERMARK_STOP parser={} watermark={} walked={} skipped={}",
+                parser_name,
+                watermark.to_rfc3339(),
+                stop,
+                window.len() - stop
+            );
+            &window[..stop]
+        }
+        None => window,
+    }
+}
+
+/// Move `parser_name`'s watermark up to the newest date in `items`, if that is newer.
+pub(crate) async fn advance_watermark(
+    storage: &Storage,
+    parser_name: &str,
+    items: &[ListingItem],
+    current: Option<DateTime<Utc>>,
+) -> Result<()> {
+    let Some(newest) = items.iter().filter_map(ListingItem::published_at).max() else {
+        return Ok(());
+    };
+    if current.is_some_and(|current| current >= newest) {
+        return Ok(());
+    }
+    storage.set_last_scrape_watermark(parser_name, newest).await
+}
+
+/// Persist metadata for the articles already uploaded before surfacing `err`, so a
+/// failure midway through a site doesn't orphan their S3 content. `err` is what gets
+/// returned either way; a failed flush is logged and attached to it.
+pub(crate) async fn flush_then_fail<T>(
+    storage: &Storage,
+    pending: &[ArticleMetadataRecord],
+    err: anyhow::Error,
+) -> Result<T> {
+    if let Err(flush_err) = storage.save_articles(pending).await {
+        error!(
+            "Failed to save metadata for {} uploaded articles: {:?}",
+            pending.len(),
+            flush_err
+        );
+        return Err(err.context(format!(
+            "saving metadata for {} uploaded articles also failed: {:#}",
+            pending.len(),
+            flush_err
+        )));
+    }
+    Err(err)
+}
+
+/// Whether a record queued earlier in this run already has canonical `url`; queued
+/// records aren't stored until `save_articles` writes them, so a stored-id lookup
+/// can't see them.
+pub(crate) fn already_pending(pending: &[ArticleMetadataRecord], url: &str) -> bool {
+    pending.iter().any(|record| record.url == url)
+}
+
+#[cfg(test)]
+mod tests {
+    use anyhow::anyhow;
+
+    use super::*;
+
+    fn article(title: &str, content_text: &str) -> Result<ScrapedArticle> {
+        Ok(ScrapedArticle {
+            canonical_url: "https://example.com/post".to_string(),
+            title: title.to_string(),
+            author: "Author".to_string(),
+            published_date: "2025-01-01".to_string(),
+            content_html: format!("<p>{}</p>", content_text),
+            content_text: content_text.to_string(),
+            images: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn parse_errors_and_hollow_articles_count_as_failed() {
+        assert!(is_failed_parse(&Err(anyhow!("No content found"))));
+        assert!(is_failed_parse(&article("  ", "Body text")));
+        assert!(is_failed_parse(&article("Untitled", "Body text")));
+        assert!(is_failed_parse(&article("A real title", " \n ")));
+    }
+
+    #[test]
+    fn titled_article_with_content_is_not_failed() {
+        assert!(!is_failed_parse(&article("A real title", "Body text")));
+    }
+
+    fn item(slug: &str, date_iso: Option<&str>) -> ListingItem {
+        ListingItem {
+            url: format!("https://example.com/{}", slug),
+            title: slug.to_string(),
+            category: "News".to_string(),
+            date_text: String::new(),
+            date_iso: date_iso.map(str::to_string),
+        }
+    }
+
+    fn slugs(items: &[ListingItem]) -> Vec<&str> {
+        items.iter().map(|i| i.title.as_str()).collect()
+    }
+
+    fn at(raw: &str) -> Option<DateTime<Utc>> {
+        Some(
+            DateTime::parse_from_rfc3339(raw)
+                .unwrap()
+                .with_timezone(&Utc),
+        )
+    }
+
+    fn listing() -> Vec<ListingItem> {
+        vec![
+            item("newest", Some("2025-03-01T00:00:00Z")),
+            item("undated", None),
+            item("same-day", Some("2025-02-01")),
+            item("older", Some("2025-01-15T00:00:00Z")),
+            item("oldest", Some("2025-01-01T00:00:00Z")),
+        ]
+    }
+
+    #[test]
+    fn walk_stops_at_first_entry_older_than_watermark() {
+        let items = listing();
+        let walked = take_until_watermark(&items, 10, at("2025-02-01T00:00:00Z"), "test");
+        assert_eq!(slugs(walked), vec!["newest", "undated", "same-day"]);
+    }
+
+    #[test]
+    fn without_watermark_only_the_limit_applies() {
+        let items = listing();
+        assert_eq!(
+            slugs(take_until_watermark(&items, 2, None, "test")),
+            vec!["newest", "undated"]
+        );
+        assert_eq!(take_until_watermark(&items, 10, None, "test").len(), 5);
+    }
+
+    #[test]
+    fn limit_applies_before_the_watermark() {
+        let items = listing();
+        let walked = take_until_watermark(&items, 1, at("2025-02-01T00:00:00Z"), "test");
+        assert_eq!(slugs(walked), vec!["newest"]);
+    }
+
+    #[test]
+    fn watermark_newer_than_everything_walks_only_undated_prefix() {
+        let items = listing();
+        assert!(take_until_watermark(&items, 10, at("2026-01-01T00:00:00Z"), "test").is_empty());
+
+        let undated_first = vec![item("undated", None), item("old", Some("2024-01-01"))];
+        let walked = take_until_watermark(&undated_first, 10, at("2025-01-01T00:00:00Z"), "test");
+        assert_eq!(slugs(walked), vec!["undated"]);
+    }
+
+    #[test]
+    fn syndicated_copy_in_the_same_run_is_detected() {
+        let queued = ArticleMetadataRecord::new(
+            "openai-research",
+            "Original",
+            "Research",
+            "Jan 1, 2025",
+            "https://openai.com/index/original/",
+            "<p>Body</p>",
+            "Body",
+            "html".to_string(),
+            "text".to_string(),
+            "images".to_string(),
+            Vec::new(),
+        );
+
+        assert!(already_pending(
+            std::slice::from_ref(&queued),
+            "https://openai.com/index/original/"
+        ));
+        assert!(!already_pending(
+            std::slice::from_ref(&queued),
+            "https://openai.com/index/other/"
+        ));
+        assert!(!already_pending(&[], "https://openai.com/index/original/"));
+    }
+}
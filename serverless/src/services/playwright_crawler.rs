@@ -1,12 +1,23 @@
+use std::collections::HashSet;
+
 use anyhow::{Context, Result};
 use playwright::Playwright;
 use reqwest::Client;
 use serde::{Deserialize, Serialize};
-use tracing::{debug, info, warn};
+use tracing::{debug, error, info, warn};
 
-use crate::models::{ListingItem, ScrapeResults, Site};
+use crate::http::{
+    build_http_client, check_content_type, is_unexpected_content_type, max_body_bytes,
+    read_body_capped, read_html,
+};
+use crate::models::{ListingItem, ScrapeResults, Site, SiteResult};
 use crate::parsers::{parse_openai_article_html, parse_openai_news_list, OPENAI_BASE};
-use crate::storage::Storage;
+use crate::services::circuit_breaker::CircuitBreaker;
+use crate::services::{
+    advance_watermark, already_pending, flush_then_fail, snapshot_failed_parse,
+    take_until_watermark,
+};
+use crate::storage::{listing_ids, ContentUpload, Storage};
 
 pub struct PlaywrightCrawlerService {
     storage: Storage,
@@ -37,13 +48,23 @@ impl PlaywrightCrawlerService {
             };
 
             if let Some(limit) = site.top_articles {
-                let added = self.scrape_top_articles(limit, site.force, &parser).await?;
-                results.new_articles += added;
+                match self.scrape_top_articles(limit, site.force, &parser).await {
+                    Ok(outcome) => results.record(&site.name, outcome),
+                    Err(e) => {
+                        error!("{}: top articles scrape failed: {:?}", site.name, e);
+                        results.record_error(&site.name, &e);
+                    }
+                }
             }
 
             if !site.articles.is_empty() {
-                let added = self.scrape_provided_articles(site, &parser).await?;
-                results.new_articles += added;
+                match self.scrape_provided_articles(site, &parser).await {
+                    Ok(outcome) => results.record(&site.name, outcome),
+                    Err(e) => {
+                        error!("{}: provided articles scrape failed: {:?}", site.name, e);
+                        results.record_error(&site.name, &e);
+                    }
+                }
             }
 
             if site.top_articles.is_none() && site.articles.is_empty() {
@@ -59,23 +80,50 @@ impl PlaywrightCrawlerService {
         limit: usize,
         force: bool,
         parser: &PlaywrightParser,
-    ) -> Result<usize> {
+    ) -> Result<SiteResult> {
         let listing = self.fetch_listing(parser).await?;
-        let mut processed = 0;
-
-        for item in listing.iter().take(limit) {
-            if !force
-                && self
-                    .storage
-                    .article_exists(parser.name(), &item.title, &item.category, &item.date_text)
-                    .await?
-            {
+        let mut outcome = SiteResult::default();
+
+        let watermark = self
+            .storage
+            .get_last_scrape_watermark(parser.name())
+            .await?;
+        let window = take_until_watermark(
+            &listing,
+            limit,
+            if force { None } else { watermark },
+            parser.name(),
+        );
+        outcome.skipped += limit.min(listing.len()) - window.len();
+
+        let ids: Vec<[String; 2]> = window
+            .iter()
+            .map(|item| {
+                listing_ids(
+                    parser.name(),
+                    &item.title,
+                    &item.category,
+                    &item.date_text,
+                    &item.url,
+                )
+            })
+            .collect();
+        let existing = if force {
+            HashSet::new()
+        } else {
+            self.storage.articles_exist(&ids.concat()).await?
+        };
+
+        let mut pending = Vec::new();
+        for (item, ids) in window.iter().zip(&ids) {
+            if ids.iter().any(|id| existing.contains(id)) {
                 info!(
                     "{}: skipping existing article \"{}\" ({})",
                     parser.name(),
                     item.title,
                     item.url
                 );
+                outcome.skipped += 1;
                 continue;
             }
 
@@ -86,33 +134,62 @@ impl PlaywrightCrawlerService {
                 item.url
             );
 
-            let article = self.parse_article(parser, &item.url).await?;
+            let article = match self.parse_article(parser, &item.url).await {
+                Ok(article) => article,
+                Err(e) if is_unexpected_content_type(&e) => {
+                    warn!(
+                        "{}: skipping non-HTML article {}: {}",
+                        parser.name(),
+                        item.url,
+                        e
+                    );
+                    outcome.skipped += 1;
+                    continue;
+                }
+                Err(e) => return flush_then_fail(&self.storage, &pending, e).await,
+            };
 
-            self.storage
-                .save_article_content(
+            if already_pending(&pending, &article.canonical_url) {
+                info!(
+                    "TAG:DUPLICATE_URL url={} already queued this run; skipping {}",
+                    article.canonical_url, item.url
+                );
+                outcome.skipped += 1;
+                continue;
+            }
+            match self
+                .storage
+                .upload_article_content(
                     parser.name(),
                     &item.title,
                     &item.category,
                     &item.date_text,
-                    &item.url,
+                    &article.canonical_url,
                     &article.content_html,
                     &article.content_text,
                     &article.images,
                 )
-                .await?;
-
-            processed += 1;
+                .await
+            {
+                Ok(ContentUpload::Uploaded(record)) => pending.push(*record),
+                Ok(ContentUpload::Skipped(_)) => outcome.skipped += 1,
+                Err(e) => return flush_then_fail(&self.storage, &pending, e).await,
+            }
         }
 
-        Ok(processed)
+        outcome.new += self.storage.save_articles(&pending).await?;
+        advance_watermark(&self.storage, parser.name(), window, watermark).await?;
+
+        Ok(outcome)
     }
 
     async fn scrape_provided_articles(
         &self,
         site: &Site,
         parser: &PlaywrightParser,
-    ) -> Result<usize> {
-        let mut new_articles = 0;
+    ) -> Result<SiteResult> {
+        let mut outcome = SiteResult::default();
+        let mut pending = Vec::new();
 
         for url in &site.articles {
             info!(
@@ -120,28 +197,55 @@ impl PlaywrightCrawlerService {
                 parser.name(),
                 url
             );
-            let article = self.parse_article(parser, url).await?;
+            let article = match self.parse_article(parser, url).await {
+                Ok(article) => article,
+                Err(e) if is_unexpected_content_type(&e) => {
+                    warn!(
+                        "{}: skipping non-HTML article {}: {}",
+                        parser.name(),
+                        url,
+                        e
+                    );
+                    outcome.skipped += 1;
+                    continue;
+                }
+                Err(e) => return flush_then_fail(&self.storage, &pending, e).await,
+            };
 
             let category = site.category.as_deref().unwrap_or("");
             let date_text = site.date_text.as_deref().unwrap_or("");
 
-            self.storage
-                .save_article_content(
+            if already_pending(&pending, &article.canonical_url) {
+                info!(
+                    "TAG:DUPLICATE_URL url={} already queued this run; skipping {}",
+                    article.canonical_url, url
+                );
+                outcome.skipped += 1;
+                continue;
+            }
+            match self
+                .storage
+                .upload_article_content(
                     parser.name(),
                     &article.title,
                     category,
                     date_text,
-                    url,
+                    &article.canonical_url,
                     &article.content_html,
                     &article.content_text,
                     &article.images,
                 )
-                .await?;
-
-            new_articles += 1;
+                .await
+            {
+                Ok(ContentUpload::Uploaded(record)) => pending.push(*record),
+                Ok(ContentUpload::Skipped(_)) => outcome.skipped += 1,
+                Err(e) => return flush_then_fail(&self.storage, &pending, e).await,
+            }
         }
 
-        Ok(new_articles)
+        outcome.new += self.storage.save_articles(&pending).await?;
+
+        Ok(outcome)
     }
 
     async fn fetch_listing(&self, parser: &PlaywrightParser) -> Result<Vec<ListingItem>> {
@@ -160,6 +264,7 @@ impl PlaywrightCrawlerService {
                         title: a.title,
                         category: a.category,
                         date_text: a.date_text,
+                        date_iso: a.date_iso,
                     })
                     .collect()
             }
@@ -170,6 +275,7 @@ impl PlaywrightCrawlerService {
                     title: a.title,
                     category: a.category,
                     date_text: a.date_text,
+                    date_iso: a.date_iso,
                 })
                 .collect(),
             PlaywrightParser::OpenAIResearch
@@ -183,6 +289,7 @@ impl PlaywrightCrawlerService {
                         title: a.title,
                         category: a.category,
                         date_text: a.date_text,
+                        date_iso: a.date_iso,
                     })
                     .collect()
             }
@@ -201,7 +308,9 @@ impl PlaywrightCrawlerService {
             .fetch_html(url)
             .await
             .with_context(|| format!("failed to fetch article via Playwright: {}", url))?;
-        parse_openai_article_html(&html)
+        let parsed = parse_openai_article_html(&html, url);
+        snapshot_failed_parse(&self.storage, url, &html, &parsed).await;
+        parsed
     }
 }
 
@@ -209,6 +318,8 @@ struct PlaywrightCrawler {
     http_client: Client,
     playwright: Option<Playwright>,
     remote_endpoint: Option<String>,
+    remote_breaker: CircuitBreaker,
+    local_breaker: CircuitBreaker,
 }
 
 impl PlaywrightCrawler {
@@ -231,41 +342,60 @@ impl PlaywrightCrawler {
             _ => None,
         };
 
-        let http_client = Client::builder()
-            .build()
-            .context("failed to build reqwest client for Playwright crawler")?;
+        let http_client =
+            build_http_client().context("failed to build reqwest client for Playwright crawler")?;
 
         Ok(Self {
             http_client,
             playwright,
             remote_endpoint,
+            remote_breaker: CircuitBreaker::from_env("playwright-remote"),
+            local_breaker: CircuitBreaker::from_env("playwright-local"),
         })
     }
 
     async fn fetch_html(&self, target_url: &str) -> Result<String> {
-        // First try remote Playwright if configured.
+        // First try remote Playwright if configured and its breaker is not open.
         if let Some(endpoint) = &self.remote_endpoint {
-            if let Ok(html) =
-                fetch_with_remote_playwright(&self.http_client, endpoint, target_url).await
-            {
-                return Ok(html);
-            } else {
-                warn!(
-                    "Remote Playwright fetch failed for {}; will try local/reqwest fallback",
+            if !self.remote_breaker.allow_request() {
+                debug!(
+                    "Remote Playwright circuit open; skipping straight to fallback for {}",
                     target_url
                 );
+            } else {
+                let result =
+                    fetch_with_remote_playwright(&self.http_client, endpoint, target_url).await;
+                self.remote_breaker.record_result(&result);
+                match result {
+                    Ok(html) => return Ok(html),
+                    Err(e) if is_unexpected_content_type(&e) => return Err(e),
+                    Err(e) => warn!(
+                        "Remote Playwright fetch failed for {}; will try local/reqwest fallback: {:#}",
+                        target_url, e
+                    ),
+                }
             }
         }
 
-        // Then try local Playwright if enabled and available.
+        // Then try local Playwright if enabled, available and not tripped.
         if let Some(playwright) = &self.playwright {
-            match fetch_with_playwright(playwright, target_url).await {
-                Ok(html) => return Ok(html),
-                Err(playwright_err) => {
-                    warn!(
-                        "Local Playwright fetch failed for {}; falling back to reqwest: {}",
-                        target_url, playwright_err
-                    );
+            if !self.local_breaker.allow_request() {
+                debug!(
+                    "Local Playwright circuit open; skipping straight to reqwest for {}",
+                    target_url
+                );
+            } else {
+                let result = fetch_with_playwright(playwright, target_url).await;
+                self.local_breaker.record_result(&result);
+                match result {
+                    Ok(html) => return Ok(html),
+                    Err(e) if is_unexpected_content_type(&e) => return Err(e),
+                    Err(playwright_err) => {
+                        warn!(
+                            "Local Playwright fetch failed for {}; falling back to reqwest: {}",
+                            target_url, playwright_err
+                        );
+                    }
                 }
             }
         }
@@ -279,12 +409,15 @@ impl PlaywrightCrawler {
             .context("fallback request failed")?
             .error_for_status()
             .context("fallback returned error status")?;
-        let body = res.text().await.context("failed to read fallback body")?;
+        let body = read_html(res)
+            .await
+            .context("failed to read fallback body")?;
         Ok(body)
     }
 }
 
 #[derive(Clone)]
+#[allow(clippy::enum_variant_names)]
 enum PlaywrightParser {
     OpenAIProductReleases,
     OpenAISecurity,
@@ -354,20 +487,32 @@ async fn fetch_with_playwright(playwright: &Playwright, url: &str) -> Result<Str
         .await
         .context("failed to open new page")?;
 
-    page.goto_builder(url)
-        .goto()
-        .await
-        .with_context(|| format!("navigation failed for {}", url))?;
+    let html = async {
+        let response = page
+            .goto_builder(url)
+            .goto()
+            .await
+            .with_context(|| format!("navigation failed for {}", url))?;
+        if let Some(response) = response {
+            let headers = response
+                .headers()
+                .await
+                .context("failed to read response headers")?;
+            let content_type = headers
+                .iter()
+                .find(|h| h.name.eq_ignore_ascii_case("content-type"))
+                .map(|h| h.value.as_str());
+            check_content_type(url, content_type)?;
+        }
 
-    let html = page
-        .content()
-        .await
-        .context("failed to read page content")?;
+        page.content().await.context("failed to read page content")
+    }
+    .await;
 
     // Close to release resources; ignore errors on close.
     let _ = browser.close().await;
 
-    Ok(html)
+    html
 }
 
 #[derive(Debug, Serialize)]
@@ -378,6 +523,9 @@ struct RemotePlaywrightRequest<'a> {
 #[derive(Debug, Deserialize)]
 struct RemotePlaywrightResponse {
     html: String,
+    /// The page's `Content-Type`, when the remote service reports it.
+    #[serde(default)]
+    content_type: Option<String>,
 }
 
 async fn fetch_with_remote_playwright(
@@ -394,10 +542,12 @@ async fn fetch_with_remote_playwright(
         .error_for_status()
         .context("remote playwright returned error status")?;
 
-    let body: RemotePlaywrightResponse = res
-        .json()
+    let raw = read_body_capped(res, max_body_bytes())
         .await
-        .context("failed to parse remote playwright response")?;
+        .context("failed to read remote playwright response")?;
+    let body: RemotePlaywrightResponse =
+        serde_json::from_str(&raw).context("failed to parse remote playwright response")?;
+    check_content_type(url, body.content_type.as_deref())?;
 
     Ok(body.html)
 }
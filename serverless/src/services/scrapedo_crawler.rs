@@ -1,10 +1,18 @@
-use anyhow::{Context, Result};
+use std::collections::HashSet;
+
+use anyhow::{bail, Context, Result};
 use reqwest::Client;
-use tracing::{debug, info, warn};
+use tracing::{debug, error, info, warn};
 
-use crate::models::{ListingItem, ScrapeResults, Site};
+use crate::http::{build_http_client, is_unexpected_content_type, read_html};
+use crate::models::{ListingItem, ScrapeResults, Site, SiteResult};
 use crate::parsers::{parse_openai_article_html, parse_openai_news_list, OPENAI_BASE};
-use crate::storage::Storage;
+use crate::services::circuit_breaker::CircuitBreaker;
+use crate::services::{
+    advance_watermark, already_pending, flush_then_fail, snapshot_failed_parse,
+    take_until_watermark,
+};
+use crate::storage::{listing_ids, ContentUpload, Storage};
 
 pub struct ScrapedoCrawlerService {
     storage: Storage,
@@ -35,13 +43,23 @@ impl ScrapedoCrawlerService {
             };
 
             if let Some(limit) = site.top_articles {
-                let added = self.scrape_top_articles(limit, site.force, &parser).await?;
-                results.new_articles += added;
+                match self.scrape_top_articles(limit, site.force, &parser).await {
+                    Ok(outcome) => results.record(&site.name, outcome),
+                    Err(e) => {
+                        error!("{}: top articles scrape failed: {:?}", site.name, e);
+                        results.record_error(&site.name, &e);
+                    }
+                }
             }
 
             if !site.articles.is_empty() {
-                let added = self.scrape_provided_articles(site, &parser).await?;
-                results.new_articles += added;
+                match self.scrape_provided_articles(site, &parser).await {
+                    Ok(outcome) => results.record(&site.name, outcome),
+                    Err(e) => {
+                        error!("{}: provided articles scrape failed: {:?}", site.name, e);
+                        results.record_error(&site.name, &e);
+                    }
+                }
             }
 
             if site.top_articles.is_none() && site.articles.is_empty() {
@@ -57,23 +75,50 @@ impl ScrapedoCrawlerService {
         limit: usize,
         force: bool,
         parser: &ScrapedoParser,
-    ) -> Result<usize> {
+    ) -> Result<SiteResult> {
         let listing = self.fetch_listing(parser).await?;
-        let mut processed = 0;
-
-        for item in listing.iter().take(limit) {
-            if !force
-                && self
-                    .storage
-                    .article_exists(parser.name(), &item.title, &item.category, &item.date_text)
-                    .await?
-            {
+        let mut outcome = SiteResult::default();
+
+        let watermark = self
+            .storage
+            .get_last_scrape_watermark(parser.name())
+            .await?;
+        let window = take_until_watermark(
+            &listing,
+            limit,
+            if force { None } else { watermark },
+            parser.name(),
+        );
+        outcome.skipped += limit.min(listing.len()) - window.len();
+
+        let ids: Vec<[String; 2]> = window
+            .iter()
+            .map(|item| {
+                listing_ids(
+                    parser.name(),
+                    &item.title,
+                    &item.category,
+                    &item.date_text,
+                    &item.url,
+                )
+            })
+            .collect();
+        let existing = if force {
+            HashSet::new()
+        } else {
+            self.storage.articles_exist(&ids.concat()).await?
+        };
+
+        let mut pending = Vec::new();
+        for (item, ids) in window.iter().zip(&ids) {
+            if ids.iter().any(|id| existing.contains(id)) {
                 info!(
                     "{}: skipping existing article \"{}\" ({})",
                     parser.name(),
                     item.title,
                     item.url
                 );
+                outcome.skipped += 1;
                 continue;
             }
 
@@ -84,58 +129,114 @@ impl ScrapedoCrawlerService {
                 item.url
             );
 
-            let article = self.parse_article(parser, &item.url).await?;
+            let article = match self.parse_article(parser, &item.url).await {
+                Ok(article) => article,
+                Err(e) if is_unexpected_content_type(&e) => {
+                    warn!(
+                        "{}: skipping non-HTML article {}: {}",
+                        parser.name(),
+                        item.url,
+                        e
+                    );
+                    outcome.skipped += 1;
+                    continue;
+                }
+                Err(e) => return flush_then_fail(&self.storage, &pending, e).await,
+            };
 
-            self.storage
-                .save_article_content(
+            if already_pending(&pending, &article.canonical_url) {
+                info!(
+                    "TAG:DUPLICATE_URL url={} already queued this run; skipping {}",
+                    article.canonical_url, item.url
+                );
+                outcome.skipped += 1;
+                continue;
+            }
+            match self
+                .storage
+                .upload_article_content(
                     parser.name(),
                     &item.title,
                     &item.category,
                     &item.date_text,
-                    &item.url,
+                    &article.canonical_url,
                     &article.content_html,
                     &article.content_text,
                     &article.images,
                 )
-                .await?;
-
-            processed += 1;
+                .await
+            {
+                Ok(ContentUpload::Uploaded(record)) => pending.push(*record),
+                Ok(ContentUpload::Skipped(_)) => outcome.skipped += 1,
+                Err(e) => return flush_then_fail(&self.storage, &pending, e).await,
+            }
         }
 
-        Ok(processed)
+        outcome.new += self.storage.save_articles(&pending).await?;
+        advance_watermark(&self.storage, parser.name(), window, watermark).await?;
+
+        Ok(outcome)
     }
 
     async fn scrape_provided_articles(
         &self,
         site: &Site,
         parser: &ScrapedoParser,
-    ) -> Result<usize> {
-        let mut new_articles = 0;
+    ) -> Result<SiteResult> {
+        let mut outcome = SiteResult::default();
+        let mut pending = Vec::new();
 
         for url in &site.articles {
             info!("{}: Scrape.do scraping provided url {}", parser.name(), url);
-            let article = self.parse_article(parser, url).await?;
+            let article = match self.parse_article(parser, url).await {
+                Ok(article) => article,
+                Err(e) if is_unexpected_content_type(&e) => {
+                    warn!(
+                        "{}: skipping non-HTML article {}: {}",
+                        parser.name(),
+                        url,
+                        e
+                    );
+                    outcome.skipped += 1;
+                    continue;
+                }
+                Err(e) => return flush_then_fail(&self.storage, &pending, e).await,
+            };
 
             let category = site.category.as_deref().unwrap_or("");
             let date_text = site.date_text.as_deref().unwrap_or("");
 
-            self.storage
-                .save_article_content(
+            if already_pending(&pending, &article.canonical_url) {
+                info!(
+                    "TAG:DUPLICATE_URL url={} already queued this run; skipping {}",
+                    article.canonical_url, url
+                );
+                outcome.skipped += 1;
+                continue;
+            }
+            match self
+                .storage
+                .upload_article_content(
                     parser.name(),
                     &article.title,
                     category,
                     date_text,
-                    url,
+                    &article.canonical_url,
                     &article.content_html,
                     &article.content_text,
                     &article.images,
                 )
-                .await?;
-
-            new_articles += 1;
+                .await
+            {
+                Ok(ContentUpload::Uploaded(record)) => pending.push(*record),
+                Ok(ContentUpload::Skipped(_)) => outcome.skipped += 1,
+                Err(e) => return flush_then_fail(&self.storage, &pending, e).await,
+            }
         }
 
-        Ok(new_articles)
+        outcome.new += self.storage.save_articles(&pending).await?;
+
+        Ok(outcome)
     }
 
     async fn fetch_listing(&self, parser: &ScrapedoParser) -> Result<Vec<ListingItem>> {
@@ -154,6 +255,7 @@ impl ScrapedoCrawlerService {
                         title: a.title,
                         category: a.category,
                         date_text: a.date_text,
+                        date_iso: a.date_iso,
                     })
                     .collect()
             }
@@ -164,6 +266,7 @@ impl ScrapedoCrawlerService {
                     title: a.title,
                     category: a.category,
                     date_text: a.date_text,
+                    date_iso: a.date_iso,
                 })
                 .collect(),
             ScrapedoParser::OpenAIResearch
@@ -177,6 +280,7 @@ impl ScrapedoCrawlerService {
                         title: a.title,
                         category: a.category,
                         date_text: a.date_text,
+                        date_iso: a.date_iso,
                     })
                     .collect()
             }
@@ -195,7 +299,9 @@ impl ScrapedoCrawlerService {
             .fetch_html(url)
             .await
             .with_context(|| format!("failed to fetch article via scrape.do: {}", url))?;
-        parse_openai_article_html(&html)
+        let parsed = parse_openai_article_html(&html, url);
+        snapshot_failed_parse(&self.storage, url, &html, &parsed).await;
+        parsed
     }
 }
 
@@ -203,6 +309,7 @@ struct ScrapedoCrawler {
     http_client: Client,
     token: String,
     endpoint: String,
+    breaker: CircuitBreaker,
 }
 
 impl ScrapedoCrawler {
@@ -213,18 +320,31 @@ impl ScrapedoCrawler {
             .unwrap_or_else(|_| "http://api.scrape.do/".to_string());
         let endpoint = endpoint.trim_end_matches('/').to_string();
 
-        let http_client = Client::builder()
-            .build()
-            .context("failed to build reqwest client for scrape.do crawler")?;
+        let http_client =
+            build_http_client().context("failed to build reqwest client for scrape.do crawler")?;
 
         Ok(Self {
             http_client,
             token,
             endpoint,
+            breaker: CircuitBreaker::from_env("scrape.do"),
         })
     }
 
     async fn fetch_html(&self, target_url: &str) -> Result<String> {
+        if !self.breaker.allow_request() {
+            bail!(
+                "scrape.do circuit open after repeated failures; skipping {}",
+                target_url
+            );
+        }
+
+        let result = self.request_html(target_url).await;
+        self.breaker.record_result(&result);
+        result
+    }
+
+    async fn request_html(&self, target_url: &str) -> Result<String> {
         let res = self
             .http_client
             .get(format!("{}/", self.endpoint))
@@ -235,12 +355,15 @@ impl ScrapedoCrawler {
             .error_for_status()
             .context("scrape.do returned error status")?;
 
-        let body = res.text().await.context("failed to read scrape.do body")?;
+        let body = read_html(res)
+            .await
+            .context("failed to read scrape.do body")?;
         Ok(body)
     }
 }
 
 #[derive(Clone)]
+#[allow(clippy::enum_variant_names)]
 enum ScrapedoParser {
     OpenAIProductReleases,
     OpenAISecurity,
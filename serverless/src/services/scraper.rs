@@ -1,24 +1,58 @@
 use anyhow::Result;
-use tracing::{debug, info, warn};
+use tracing::{debug, error, info, warn};
 
-use crate::models::{ScrapeResults, Site};
+use crate::http::is_unexpected_content_type;
+use crate::models::{ScrapeResults, ScrapedArticle, Site, SiteResult};
+use crate::parsers::config_parser::{load_config_parsers, ConfigParser};
 use crate::parsers::openai_company_announcements::OpenAICompanyAnnouncementsParser;
 use crate::parsers::openai_engineering::OpenAIEngineeringParser;
 use crate::parsers::openai_product_releases::OpenAIProductReleasesParser;
 use crate::parsers::openai_research::OpenAIResearchParser;
 use crate::parsers::openai_safety_alignment::OpenAISafetyAlignmentParser;
 use crate::parsers::openai_security::OpenAISecurityParser;
-use crate::parsers::Parser;
-use crate::storage::Storage;
+use crate::parsers::{fetch_html, Parser};
+use crate::services::{
+    advance_watermark, already_pending, flush_then_fail, snapshot_failed_parse,
+    take_until_watermark,
+};
+use crate::storage::{ContentUpload, Storage};
+
+/// Site names with a built-in parser in `parser_for_site`.
+const SUPPORTED_SITES: &[&str] = &[
+    "openai-product-releases",
+    "openai-company-announcements",
+    "openai-research",
+    "openai-engineering",
+    "openai-safety-alignment",
+    "openai-security",
+];
+
+/// Built-in parser for one of `SUPPORTED_SITES`.
+fn builtin_parser(name: &str) -> Option<Box<dyn Parser>> {
+    match name {
+        "openai-product-releases" => Some(Box::new(OpenAIProductReleasesParser::new())),
+        "openai-company-announcements" => Some(Box::new(OpenAICompanyAnnouncementsParser::new())),
+        "openai-research" => Some(Box::new(OpenAIResearchParser::new())),
+        "openai-engineering" => Some(Box::new(OpenAIEngineeringParser::new())),
+        "openai-safety-alignment" => Some(Box::new(OpenAISafetyAlignmentParser::new())),
+        "openai-security" => Some(Box::new(OpenAISecurityParser::new())),
+        _ => None,
+    }
+}
 
 pub struct ScraperService {
     storage: Storage,
+    config_parsers: Vec<ConfigParser>,
 }
 
 impl ScraperService {
     pub async fn new() -> Result<Self> {
         let storage = Storage::from_env().await?;
-        Ok(Self { storage })
+        let config_parsers = load_config_parsers().await?;
+        Ok(Self {
+            storage,
+            config_parsers,
+        })
     }
 
     /// Execute scraping for the provided sites. Currently stubbed; integrate
@@ -37,17 +71,26 @@ impl ScraperService {
             let parser = self.parser_for_site(&site.name);
 
             if let Some(limit) = site.top_articles {
-                let added = self
+                match self
                     .scrape_top_articles(site, limit, parser.as_deref())
-                    .await?;
-                results.new_articles += added;
+                    .await
+                {
+                    Ok(outcome) => results.record(&site.name, outcome),
+                    Err(e) => {
+                        error!("{}: top articles scrape failed: {:?}", site.name, e);
+                        results.record_error(&site.name, &e);
+                    }
+                }
             }
 
             if !site.articles.is_empty() {
-                let added = self
-                    .scrape_provided_articles(site, parser.as_deref())
-                    .await?;
-                results.new_articles += added;
+                match self.scrape_provided_articles(site, parser.as_deref()).await {
+                    Ok(outcome) => results.record(&site.name, outcome),
+                    Err(e) => {
+                        error!("{}: provided articles scrape failed: {:?}", site.name, e);
+                        results.record_error(&site.name, &e);
+                    }
+                }
             }
 
             if site.top_articles.is_none() && site.articles.is_empty() {
@@ -58,18 +101,22 @@ impl ScraperService {
         Ok(results)
     }
 
+    /// Built-in site names followed by any config-driven parsers.
+    pub fn supported_sites(&self) -> Vec<&str> {
+        SUPPORTED_SITES
+            .iter()
+            .copied()
+            .chain(self.config_parsers.iter().map(|p| p.name()))
+            .collect()
+    }
+
     fn parser_for_site(&self, name: &str) -> Option<Box<dyn Parser>> {
-        match name {
-            "openai-product-releases" => Some(Box::new(OpenAIProductReleasesParser::new())),
-            "openai-company-announcements" => {
-                Some(Box::new(OpenAICompanyAnnouncementsParser::new()))
-            }
-            "openai-research" => Some(Box::new(OpenAIResearchParser::new())),
-            "openai-engineering" => Some(Box::new(OpenAIEngineeringParser::new())),
-            "openai-safety-alignment" => Some(Box::new(OpenAISafetyAlignmentParser::new())),
-            "openai-security" => Some(Box::new(OpenAISecurityParser::new())),
-            _ => None,
-        }
+        builtin_parser(name).or_else(|| {
+            self.config_parsers
+                .iter()
+                .find(|p| p.name() == name)
+                .map(|p| Box::new(p.clone()) as Box<dyn Parser>)
+        })
     }
 
     async fn scrape_top_articles(
@@ -77,38 +124,83 @@ impl ScraperService {
         site: &Site,
         limit: usize,
         parser: Option<&dyn Parser>,
-    ) -> Result<usize> {
+    ) -> Result<SiteResult> {
         let Some(parser) = parser else {
             warn!(
-                "Top-article scraping not implemented for {} (requested {})",
-                site.name, limit
+                "Top-article scraping not implemented for {} (requested {}); supported sites: {}",
+                site.name,
+                limit,
+                self.supported_sites().join(", ")
             );
-            return Ok(0);
+            return Ok(SiteResult::default());
         };
 
         let listing = parser.parse_listing().await?;
-        let mut processed = 0;
+        let mut outcome = SiteResult {
+            listing: Some(listing.outcome),
+            ..SiteResult::default()
+        };
 
-        for item in listing.iter().take(limit) {
+        let watermark = self
+            .storage
+            .get_last_scrape_watermark(parser.name())
+            .await?;
+        let window = take_until_watermark(
+            &listing.items,
+            limit,
+            if site.force { None } else { watermark },
+            parser.name(),
+        );
+        outcome.skipped += limit.min(listing.items.len()) - window.len();
+
+        let mut pending = Vec::new();
+        for item in window {
             info!(
                 "TAG:LISTING NAME {}: listing -> {}",
                 parser.name(),
                 item.url
             );
-            let article = parser.parse_article(&item.url).await?;
+            let article = match self.parse_article(parser, &item.url).await {
+                Ok(article) => article,
+                Err(e) if is_unexpected_content_type(&e) => {
+                    warn!(
+                        "{}: skipping non-HTML article {}: {}",
+                        parser.name(),
+                        item.url,
+                        e
+                    );
+                    outcome.skipped += 1;
+                    continue;
+                }
+                Err(e) => return flush_then_fail(&self.storage, &pending, e).await,
+            };
+            if already_pending(&pending, &article.canonical_url) {
+                info!(
+                    "TAG:DUPLICATE_URL url={} already queued this run; skipping {}",
+                    article.canonical_url, item.url
+                );
+                outcome.skipped += 1;
+                continue;
+            }
             let images = article.images.join(", ");
-            self.storage
-                .save_article_content(
+            match self
+                .storage
+                .upload_article_content(
                     parser.name(),
                     &item.title,
                     &item.category,
                     &item.date_text,
-                    &item.url,
+                    &article.canonical_url,
                     &article.content_html,
                     &article.content_text,
                     &article.images,
                 )
-                .await?;
+                .await
+            {
+                Ok(ContentUpload::Uploaded(record)) => pending.push(*record),
+                Ok(ContentUpload::Skipped(_)) => outcome.skipped += 1,
+                Err(e) => return flush_then_fail(&self.storage, &pending, e).await,
+            }
             info!(
                 "TAG:ARTICLE {}: parsed '{}' ({} chars) | content_html{} |, | images: {} |",
                 parser.name(),
@@ -117,42 +209,73 @@ impl ScraperService {
                 article.content_html,
                 images
             );
-            processed += 1;
         }
 
-        Ok(processed)
+        outcome.new += self.storage.save_articles(&pending).await?;
+        advance_watermark(&self.storage, parser.name(), window, watermark).await?;
+
+        Ok(outcome)
     }
 
     async fn scrape_provided_articles(
         &self,
         site: &Site,
         parser: Option<&dyn Parser>,
-    ) -> Result<usize> {
+    ) -> Result<SiteResult> {
         let Some(parser) = parser else {
             warn!(
-                "Article scraping not implemented for {}; skipping provided urls",
-                site.name
+                "Article scraping not implemented for {}; skipping provided urls (supported sites: {})",
+                site.name,
+                self.supported_sites().join(", ")
             );
-            return Ok(0);
+            return Ok(SiteResult::default());
         };
 
-        let mut new_articles = 0;
+        let mut outcome = SiteResult::default();
+        let mut pending = Vec::new();
 
         for url in &site.articles {
             info!("{}: scraping provided url {}", parser.name(), url);
-            let article = parser.parse_article(url).await?;
-            self.storage
-                .save_article_content(
+            let article = match self.parse_article(parser, url).await {
+                Ok(article) => article,
+                Err(e) if is_unexpected_content_type(&e) => {
+                    warn!(
+                        "{}: skipping non-HTML article {}: {}",
+                        parser.name(),
+                        url,
+                        e
+                    );
+                    outcome.skipped += 1;
+                    continue;
+                }
+                Err(e) => return flush_then_fail(&self.storage, &pending, e).await,
+            };
+            if already_pending(&pending, &article.canonical_url) {
+                info!(
+                    "TAG:DUPLICATE_URL url={} already queued this run; skipping {}",
+                    article.canonical_url, url
+                );
+                outcome.skipped += 1;
+                continue;
+            }
+            match self
+                .storage
+                .upload_article_content(
                     parser.name(),
                     &article.title,
                     "",
                     &article.published_date,
-                    url,
+                    &article.canonical_url,
                     &article.content_html,
                     &article.content_text,
                     &article.images,
                 )
-                .await?;
+                .await
+            {
+                Ok(ContentUpload::Uploaded(record)) => pending.push(*record),
+                Ok(ContentUpload::Skipped(_)) => outcome.skipped += 1,
+                Err(e) => return flush_then_fail(&self.storage, &pending, e).await,
+            }
             info!(
                 "{}: parsed '{}' ({} chars) | content_html{} |",
                 parser.name(),
@@ -160,9 +283,33 @@ impl ScraperService {
                 article.content_text.len(),
                 article.content_html
             );
-            new_articles += 1;
         }
 
-        Ok(new_articles)
+        outcome.new += self.storage.save_articles(&pending).await?;
+
+        Ok(outcome)
+    }
+
+    /// Fetch and parse one article, snapshotting the HTML when the parse fails.
+    async fn parse_article(&self, parser: &dyn Parser, url: &str) -> Result<ScrapedArticle> {
+        let html = fetch_html(parser.client(), url).await?;
+        let parsed = parser.parse_article_from_html(&html, url);
+        snapshot_failed_parse(&self.storage, url, &html, &parsed).await;
+        parsed
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_supported_site_has_a_builtin_parser() {
+        for &site in SUPPORTED_SITES {
+            let parser = builtin_parser(site)
+                .unwrap_or_else(|| panic!("{} is listed but has no parser", site));
+            assert_eq!(parser.name(), site);
+        }
+        assert!(builtin_parser("not-a-site").is_none());
     }
 }
@@ -0,0 +1,74 @@
+/// Characters of the article id appended to a slug that another article already owns.
+const COLLISION_SUFFIX_LEN: usize = 8;
+
+/// Lowercase ASCII slug: alphanumerics are kept and every other run of characters
+/// becomes a single `-`, with none leading or trailing.
+pub fn slugify(input: &str) -> String {
+    let mut out = String::new();
+    let mut last_dash = false;
+
+    for c in input.to_ascii_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c);
+            last_dash = false;
+        } else if !last_dash {
+            out.push('-');
+            last_dash = true;
+        }
+    }
+
+    out.trim_matches('-').to_string()
+}
+
+/// Readable public slug for an article, from its title and listing date
+/// (e.g. "introducing-gpt-5-aug-7-2025"). Falls back to `id` when both are blank.
+pub fn article_slug(title: &str, date_text: &str, id: &str) -> String {
+    let slug = slugify(&format!("{} {}", title, date_text));
+    if slug.is_empty() {
+        id.to_string()
+    } else {
+        slug
+    }
+}
+
+/// `slug` made unique for article `id` by appending the start of the id.
+pub fn disambiguate_slug(slug: &str, id: &str) -> String {
+    let suffix: String = id.chars().take(COLLISION_SUFFIX_LEN).collect();
+    format!("{}-{}", slug, suffix)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugify_collapses_punctuation_and_case() {
+        let cases = [
+            ("Introducing GPT-5", "introducing-gpt-5"),
+            ("  Hello,   World!  ", "hello-world"),
+            ("Café & Crème", "caf-cr-me"),
+            ("---", ""),
+            ("", ""),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(slugify(input), expected, "input {:?}", input);
+        }
+    }
+
+    #[test]
+    fn article_slug_combines_title_and_date() {
+        assert_eq!(
+            article_slug("Introducing GPT-5", "Aug 7, 2025", "abc"),
+            "introducing-gpt-5-aug-7-2025"
+        );
+        assert_eq!(article_slug("", "", "abc123"), "abc123");
+    }
+
+    #[test]
+    fn collisions_get_an_id_suffix() {
+        assert_eq!(
+            disambiguate_slug("introducing-gpt-5", "0123456789abcdef"),
+            "introducing-gpt-5-01234567"
+        );
+    }
+}
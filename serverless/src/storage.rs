@@ -1,15 +1,37 @@
-use std::collections::HashMap;
+use std::borrow::Cow;
+use std::collections::{HashMap, HashSet};
 
-use anyhow::Result;
-use aws_sdk_dynamodb::{types::AttributeValue, Client as DynamoClient};
+use std::future::Future;
+use std::time::Duration;
+
+use anyhow::{bail, Context, Result};
+use aws_sdk_dynamodb::{
+    error::ProvideErrorMetadata,
+    types::{AttributeValue, KeysAndAttributes, PutRequest, WriteRequest},
+    Client as DynamoClient,
+};
 use aws_sdk_s3::Client as S3Client;
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
 use serde_json;
 use sha2::{Digest, Sha256};
-use tracing::info;
+use tracing::{info, warn};
 
 use crate::config::Config;
-use crate::models::ListingItem;
+use crate::models::{
+    reading_stats, truncate_on_char_boundary, ListingItem, ScrapeReport, SiteResult,
+};
+use crate::sanitize::sanitize_html;
+use crate::slug::{article_slug, disambiguate_slug};
+use crate::tags::{extract_tags, DEFAULT_TAG_COUNT};
+
+/// DynamoDB caps `batch_write_item` at 25 requests.
+const BATCH_WRITE_LIMIT: usize = 25;
+/// Articles table GSI keyed by `slug`.
+const SLUG_INDEX: &str = "slug-index";
+/// DynamoDB caps `batch_get_item` at 100 keys.
+const BATCH_GET_LIMIT: usize = 100;
+const BATCH_MAX_ATTEMPTS: u32 = 5;
 
 #[derive(Debug, Clone)]
 pub struct ArticleMetadataRecord {
@@ -19,17 +41,65 @@ pub struct ArticleMetadataRecord {
     pub category: String,
     pub date_text: String,
     pub url: String,
+    /// Readable public identifier, unique across articles; `id` stays the stable key.
+    pub slug: String,
     pub html_key: String,
     pub text_key: String,
     pub images_key: String,
     pub images: Vec<String>,
+    pub word_count: usize,
+    pub reading_time: String,
+    /// Keyword tags extracted from the article text.
+    pub tags: Vec<String>,
+    /// Stored HTML or text was cut to `MAX_CONTENT_BYTES`.
+    pub truncated: bool,
+    /// SHA-256 of the uploaded HTML, text and image list; a re-scrape with the same
+    /// hash skips both the S3 uploads and the metadata write.
+    pub content_hash: String,
     pub updated_at: i64,
 }
 
+/// What saving one metadata record did.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SaveOutcome {
+    Written,
+    /// The stored `content_hash` matched, so nothing was uploaded and the item (and
+    /// its `updated_at`) was left alone.
+    NotChanged,
+}
+
+/// What `upload_article_content` did with one article.
+#[derive(Debug)]
+pub enum ContentUpload {
+    /// Content is in S3; persist this record with `save_articles`.
+    Uploaded(Box<ArticleMetadataRecord>),
+    /// Nothing was uploaded, for the given reason.
+    Skipped(SaveOutcome),
+}
+
+impl SaveOutcome {
+    fn compare(content_hash: &str, stored_hash: Option<&str>) -> Self {
+        if stored_hash == Some(content_hash) {
+            SaveOutcome::NotChanged
+        } else {
+            SaveOutcome::Written
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize)]
+pub struct BackfillSummary {
+    pub updated: usize,
+    pub skipped: usize,
+}
+
 impl ArticleMetadataRecord {
+    #[allow(clippy::too_many_arguments)]
     pub fn from_listing(
         parser_name: &str,
         listing: &ListingItem,
+        content_html: &str,
+        content_text: &str,
         html_key: String,
         text_key: String,
         images_key: String,
@@ -41,6 +111,8 @@ impl ArticleMetadataRecord {
             &listing.category,
             &listing.date_text,
             &listing.url,
+            content_html,
+            content_text,
             html_key,
             text_key,
             images_key,
@@ -48,18 +120,25 @@ impl ArticleMetadataRecord {
         )
     }
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         parser_name: &str,
         title: &str,
         category: &str,
         date_text: &str,
         url: &str,
+        content_html: &str,
+        content_text: &str,
         html_key: String,
         text_key: String,
         images_key: String,
         images: Vec<String>,
     ) -> Self {
-        let id = generate_id(parser_name, title, category, date_text);
+        let id = generate_id(url);
+        let content_hash = content_hash(content_html, content_text, &images);
+        let (word_count, reading_time) = reading_stats(content_text);
+        let tags = extract_tags(content_text, DEFAULT_TAG_COUNT);
+        let slug = article_slug(title, date_text, &id);
 
         Self {
             id,
@@ -68,10 +147,16 @@ impl ArticleMetadataRecord {
             category: category.to_string(),
             date_text: date_text.to_string(),
             url: url.to_string(),
+            slug,
             html_key,
             text_key,
             images_key,
             images,
+            word_count,
+            reading_time,
+            tags,
+            truncated: false,
+            content_hash,
             updated_at: Utc::now().timestamp(),
         }
     }
@@ -81,7 +166,12 @@ pub struct Storage {
     dynamo: DynamoClient,
     s3: S3Client,
     table_name: String,
+    runs_table_name: String,
+    watermarks_table_name: String,
     bucket_name: String,
+    /// Set only when `SNAPSHOT_ON_PARSE_FAILURE` is enabled and `SNAPSHOT_BUCKET` is configured.
+    debug_snapshot_bucket: Option<String>,
+    max_content_bytes: usize,
 }
 
 impl Storage {
@@ -95,22 +185,125 @@ impl Storage {
             dynamo,
             s3,
             table_name: config.table_name,
+            runs_table_name: config.runs_table_name,
+            watermarks_table_name: config.watermarks_table_name,
             bucket_name: config.bucket_name,
+            debug_snapshot_bucket: config
+                .snapshot_bucket
+                .filter(|_| config.snapshot_on_parse_failure),
+            max_content_bytes: config.max_content_bytes,
         })
     }
 
-    pub async fn article_exists(
+    /// Which of `ids` already have a metadata record.
+    pub async fn articles_exist(&self, ids: &[String]) -> Result<HashSet<String>> {
+        let items = self.batch_get_metadata(ids, "id").await?;
+        Ok(items
+            .iter()
+            .filter_map(|item| item.get("id").and_then(|v| v.as_s().ok()).cloned())
+            .collect())
+    }
+
+    /// The id an article is stored under plus its stored `content_hash`: `id` when that
+    /// item exists, else an item saved under the pre-canonical `legacy` id (which keeps
+    /// its key), else `id` with no hash.
+    async fn resolve_stored_id(&self, id: &str, legacy: &str) -> Result<(String, Option<String>)> {
+        if let Some(item) = self.stored_hash_item(id).await? {
+            return Ok((id.to_string(), string_attr(&item, "content_hash")));
+        }
+        if let Some(item) = self.stored_hash_item(legacy).await? {
+            info!("TAG:LEGACY_ID saving id={} under legacy id={}", id, legacy);
+            return Ok((legacy.to_string(), string_attr(&item, "content_hash")));
+        }
+        Ok((id.to_string(), None))
+    }
+
+    /// The `id`/`content_hash` attributes of the metadata item for `id`, if it exists.
+    async fn stored_hash_item(&self, id: &str) -> Result<Option<HashMap<String, AttributeValue>>> {
+        Ok(self
+            .dynamo
+            .get_item()
+            .table_name(&self.table_name)
+            .key("id", AttributeValue::S(id.to_string()))
+            .projection_expression("id, content_hash")
+            .send()
+            .await?
+            .item)
+    }
+
+    /// Fetch the `projection` attributes of the metadata items for `ids` via
+    /// `batch_get_item`, chunked at the DynamoDB limit (unprocessed keys are retried
+    /// with backoff). Missing ids are simply absent from the result.
+    async fn batch_get_metadata(
         &self,
-        parser_name: &str,
-        title: &str,
-        category: &str,
-        date_text: &str,
-    ) -> Result<bool> {
-        let id = generate_id(parser_name, title, category, date_text);
-        self.metadata_exists(&id).await
+        ids: &[String],
+        projection: &str,
+    ) -> Result<Vec<HashMap<String, AttributeValue>>> {
+        let mut found = Vec::new();
+
+        for chunk in key_batches(ids, BATCH_GET_LIMIT) {
+            let mut pending = Some(
+                KeysAndAttributes::builder()
+                    .set_keys(Some(
+                        chunk
+                            .iter()
+                            .map(|id| {
+                                HashMap::from([(
+                                    "id".to_string(),
+                                    AttributeValue::S(id.to_string()),
+                                )])
+                            })
+                            .collect(),
+                    ))
+                    .projection_expression(projection)
+                    .build()?,
+            );
+
+            let mut attempt = 0;
+            while let Some(keys) = pending.take() {
+                if attempt > 0 {
+                    tokio::time::sleep(Duration::from_millis(100 * 2u64.pow(attempt))).await;
+                }
+                if attempt >= BATCH_MAX_ATTEMPTS {
+                    bail!(
+                        "{} keys still unprocessed after {} batch_get_item attempts",
+                        keys.keys().len(),
+                        attempt
+                    );
+                }
+
+                let output = self
+                    .dynamo
+                    .batch_get_item()
+                    .request_items(&self.table_name, keys)
+                    .send()
+                    .await?;
+
+                found.extend(
+                    output
+                        .responses
+                        .and_then(|mut responses| responses.remove(&self.table_name))
+                        .unwrap_or_default(),
+                );
+
+                pending = output
+                    .unprocessed_keys
+                    .and_then(|mut unprocessed| unprocessed.remove(&self.table_name))
+                    .filter(|keys| !keys.keys().is_empty());
+                attempt += 1;
+            }
+        }
+
+        Ok(found)
     }
 
-    pub async fn save_article_content(
+    /// Upload an article's HTML/text/images to S3 and return the metadata record to
+    /// persist, without writing it. Pair with `save_articles` to batch the DynamoDB writes.
+    /// `url` is the canonical URL and determines the id, so copies of one article listed
+    /// under several categories share a record. Uploads nothing when the stored
+    /// `content_hash` matches, so an unchanged re-scrape adds no object versions.
+    #[allow(clippy::too_many_arguments)]
+    pub async fn upload_article_content(
         &self,
         parser_name: &str,
         title: &str,
@@ -120,134 +313,470 @@ impl Storage {
         content_html: &str,
         content_text: &str,
         images: &[String],
-    ) -> Result<()> {
-        let id = generate_id(parser_name, title, category, date_text);
-        let slug = {
-            let candidate = slugify(&format!("{}-{}", category, title));
-            if candidate.is_empty() {
-                id.clone()
-            } else {
-                candidate
-            }
-        };
-        let base_prefix = format!("{}/{}", parser_name, slug);
-
-        let html_key = format!("{}/content.html", base_prefix);
-        let text_key = format!("{}/content.txt", base_prefix);
-        let images_key = format!("{}/images.json", base_prefix);
-
-        self.upload_string(&html_key, content_html, "text/html")
-            .await?;
-        self.upload_string(&text_key, content_text, "text/plain")
+    ) -> Result<ContentUpload> {
+        let (id, stored_hash) = self
+            .resolve_stored_id(
+                &generate_id(url),
+                &legacy_id(parser_name, title, category, date_text),
+            )
             .await?;
 
-        let images_payload = serde_json::to_vec(images)?;
-        self.upload_bytes(&images_key, &images_payload, "application/json")
-            .await?;
+        let content = cap_content(content_html, content_text, self.max_content_bytes);
+        let [html_key, text_key, images_key] = content_keys(parser_name, &id);
 
-        let metadata = ArticleMetadataRecord::new(
+        let mut metadata = ArticleMetadataRecord::new(
             parser_name,
             title,
             category,
             date_text,
             url,
+            &content.html,
+            content.text,
             html_key.clone(),
             text_key.clone(),
             images_key.clone(),
             images.to_vec(),
         );
+        metadata.id = id;
 
-        self.upsert_article_metadata(&metadata).await
+        if SaveOutcome::compare(&metadata.content_hash, stored_hash.as_deref())
+            == SaveOutcome::NotChanged
+        {
+            info!(
+                "TAG:DYNAMO_UNCHANGED content unchanged id={} parser={} title=\"{}\"",
+                metadata.id, metadata.parser, metadata.title
+            );
+            return Ok(ContentUpload::Skipped(SaveOutcome::NotChanged));
+        }
+
+        if content.truncated {
+            warn!(
+                url,
+                max_bytes = self.max_content_bytes,
+                "TAG:CONTENT_TRUNCATED article content exceeds limit"
+            );
+        }
+
+        self.upload_string(&html_key, &content.html, "text/html")
+            .await?;
+        self.upload_string(&text_key, content.text, "text/plain")
+            .await?;
+
+        let images_payload = serde_json::to_vec(images)?;
+        self.upload_bytes(&images_key, &images_payload, "application/json")
+            .await?;
+
+        metadata.truncated = content.truncated;
+        // After the hash check, so unchanged re-scrapes never query the slug index.
+        metadata.slug = self.unique_slug(&metadata.id, &metadata.slug).await?;
+
+        Ok(ContentUpload::Uploaded(Box::new(metadata)))
     }
 
-    async fn upsert_article_metadata(&self, metadata: &ArticleMetadataRecord) -> Result<()> {
-        let exists = self.metadata_exists(&metadata.id).await?;
+    /// Write metadata records with `batch_write_item` (chunked at 25, unprocessed items
+    /// retried) instead of one `put_item` per article, returning how many were written.
+    /// Unchanged articles were already filtered out by `upload_article_content`.
+    pub async fn save_articles(&self, records: &[ArticleMetadataRecord]) -> Result<usize> {
+        if records.is_empty() {
+            return Ok(0);
+        }
 
-        let mut item = HashMap::new();
-        item.insert("id".to_string(), AttributeValue::S(metadata.id.clone()));
-        item.insert(
-            "parser".to_string(),
-            AttributeValue::S(metadata.parser.clone()),
+        // batch_write_item rejects a batch with two puts for the same key; keep the last.
+        let mut by_id: HashMap<&str, &ArticleMetadataRecord> = HashMap::new();
+        for metadata in records {
+            by_id.insert(&metadata.id, metadata);
+        }
+        self.batch_put_items(by_id.values().map(|m| metadata_item(m)).collect())
+            .await?;
+
+        for metadata in by_id.values() {
+            info!(
+                "TAG:DYNAMO_UPSERT saved article metadata id={} parser={} title=\"{}\"",
+                metadata.id, metadata.parser, metadata.title
+            );
+        }
+
+        Ok(by_id.len())
+    }
+
+    /// Recompute `word_count`/`reading_time` for every stored article from its S3 text,
+    /// give articles saved before slugs existed a unique `slug`, and write back only the
+    /// items whose values changed. Safe to re-run.
+    pub async fn backfill_metadata(&self) -> Result<BackfillSummary> {
+        let mut summary = BackfillSummary::default();
+        let mut changed = Vec::new();
+        // Slugs handed out by this run; pending writes aren't in the slug index yet.
+        let mut assigned = HashSet::new();
+        let mut items = self
+            .dynamo
+            .scan()
+            .table_name(&self.table_name)
+            .into_paginator()
+            .items()
+            .send();
+
+        while let Some(item) = items.next().await {
+            let mut item = item.context("failed to scan articles table")?;
+            let id = item
+                .get("id")
+                .and_then(|v| v.as_s().ok())
+                .cloned()
+                .unwrap_or_default();
+            let Some(text_key) = item.get("text_key").and_then(|v| v.as_s().ok()).cloned() else {
+                summary.skipped += 1;
+                continue;
+            };
+
+            let text = match self.download_string(&text_key).await {
+                Ok(text) => text,
+                Err(e) => {
+                    warn!(
+                        "TAG:BACKFILL skipping id={} (failed to read {}): {:?}",
+                        id, text_key, e
+                    );
+                    summary.skipped += 1;
+                    continue;
+                }
+            };
+
+            let (word_count, reading_time) = reading_stats(&text);
+            let stored_count = item
+                .get("word_count")
+                .and_then(|v| v.as_n().ok())
+                .and_then(|n| n.parse::<usize>().ok());
+            let stored_time = item.get("reading_time").and_then(|v| v.as_s().ok());
+            let tags = extract_tags(&text, DEFAULT_TAG_COUNT);
+            let stored_tags: Option<Vec<&String>> = item
+                .get("tags")
+                .and_then(|v| v.as_l().ok())
+                .map(|l| l.iter().filter_map(|t| t.as_s().ok()).collect());
+
+            let missing_slug = item
+                .get("slug")
+                .and_then(|v| v.as_s().ok())
+                .is_none_or(|slug| slug.is_empty());
+
+            if stored_count == Some(word_count)
+                && stored_time == Some(&reading_time)
+                && stored_tags == Some(tags.iter().collect())
+                && !missing_slug
+            {
+                summary.skipped += 1;
+                continue;
+            }
+
+            if missing_slug {
+                let field = |name: &str| {
+                    item.get(name)
+                        .and_then(|v| v.as_s().ok())
+                        .cloned()
+                        .unwrap_or_default()
+                };
+                let candidate = article_slug(&field("title"), &field("date_text"), &id);
+                let mut slug = self.unique_slug(&id, &candidate).await?;
+                if !assigned.insert(slug.clone()) {
+                    slug = disambiguate_slug(&candidate, &id);
+                    assigned.insert(slug.clone());
+                }
+                info!("TAG:BACKFILL assigned slug={} id={}", slug, id);
+                item.insert("slug".to_string(), AttributeValue::S(slug));
+            }
+
+            item.insert(
+                "word_count".to_string(),
+                AttributeValue::N(word_count.to_string()),
+            );
+            item.insert("reading_time".to_string(), AttributeValue::S(reading_time));
+            item.insert("tags".to_string(), string_list(&tags));
+            changed.push(item);
+            summary.updated += 1;
+
+            if changed.len() >= BATCH_WRITE_LIMIT {
+                self.batch_put_items(std::mem::take(&mut changed)).await?;
+            }
+        }
+
+        self.batch_put_items(changed).await?;
+
+        info!(
+            "TAG:BACKFILL completed updated={} skipped={}",
+            summary.updated, summary.skipped
         );
-        item.insert(
-            "title".to_string(),
-            AttributeValue::S(metadata.title.clone()),
+
+        Ok(summary)
+    }
+
+    /// `(id, url)` of every stored article, for source link checks.
+    pub async fn list_source_urls(&self) -> Result<Vec<(String, String)>> {
+        let mut urls = Vec::new();
+        let mut items = self
+            .dynamo
+            .scan()
+            .table_name(&self.table_name)
+            .projection_expression("id, #url")
+            .expression_attribute_names("#url", "url")
+            .into_paginator()
+            .items()
+            .send();
+
+        while let Some(item) = items.next().await {
+            let item = item.context("failed to scan articles table")?;
+            let id = item.get("id").and_then(|v| v.as_s().ok());
+            let url = item.get("url").and_then(|v| v.as_s().ok());
+            if let (Some(id), Some(url)) = (id, url) {
+                urls.push((id.clone(), url.clone()));
+            }
+        }
+
+        Ok(urls)
+    }
+
+    /// Record a link check result on an existing article without touching `updated_at`.
+    pub async fn set_source_status(&self, id: &str, status: &str, checked_at: i64) -> Result<()> {
+        self.dynamo
+            .update_item()
+            .table_name(&self.table_name)
+            .key("id", AttributeValue::S(id.to_string()))
+            .update_expression("SET source_status = :status, last_checked = :checked")
+            .condition_expression("attribute_exists(id)")
+            .expression_attribute_values(":status", AttributeValue::S(status.to_string()))
+            .expression_attribute_values(":checked", AttributeValue::N(checked_at.to_string()))
+            .send()
+            .await
+            .with_context(|| format!("failed to record source status for id={}", id))?;
+
+        Ok(())
+    }
+
+    /// Write items with `batch_write_item`, chunked at the DynamoDB limit and retrying
+    /// any `UnprocessedItems` with exponential backoff.
+    async fn batch_put_items(&self, items: Vec<HashMap<String, AttributeValue>>) -> Result<()> {
+        let requests = items
+            .into_iter()
+            .map(|item| {
+                let put = PutRequest::builder().set_item(Some(item)).build()?;
+                Ok(WriteRequest::builder().put_request(put).build())
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        write_batches(requests, |batch| async move {
+            let output = self
+                .dynamo
+                .batch_write_item()
+                .request_items(&self.table_name, batch)
+                .send()
+                .await?;
+
+            Ok(output
+                .unprocessed_items
+                .and_then(|mut unprocessed| unprocessed.remove(&self.table_name))
+                .unwrap_or_default())
+        })
+        .await
+    }
+
+    /// Newest listing date seen by the last successful top-N scrape of `parser_name`.
+    /// Stored in the watermarks table, keyed by `parser`.
+    pub async fn get_last_scrape_watermark(
+        &self,
+        parser_name: &str,
+    ) -> Result<Option<DateTime<Utc>>> {
+        let result = self
+            .dynamo
+            .get_item()
+            .table_name(&self.watermarks_table_name)
+            .key("parser", AttributeValue::S(parser_name.to_string()))
+            .send()
+            .await?;
+
+        let watermark = result
+            .item()
+            .and_then(|item| item.get("watermark"))
+            .and_then(|value| value.as_s().ok())
+            .and_then(|raw| DateTime::parse_from_rfc3339(raw).ok())
+            .map(|d| d.with_timezone(&Utc));
+
+        Ok(watermark)
+    }
+
+    pub async fn set_last_scrape_watermark(
+        &self,
+        parser_name: &str,
+        watermark: DateTime<Utc>,
+    ) -> Result<()> {
+        self.dynamo
+            .put_item()
+            .table_name(&self.watermarks_table_name)
+            .item("parser", AttributeValue::S(parser_name.to_string()))
+            .item("watermark", AttributeValue::S(watermark.to_rfc3339()))
+            .item(
+                "updated_at",
+                AttributeValue::N(Utc::now().timestamp().to_string()),
+            )
+            .send()
+            .await?;
+
+        info!(
+            "TAG:WATERMARK_SET parser={} watermark={}",
+            parser_name,
+            watermark.to_rfc3339()
         );
+        Ok(())
+    }
+
+    /// Persist a scrape run report to the runs table, keyed by `run_id`.
+    pub async fn save_run_report(&self, report: &ScrapeReport) -> Result<()> {
+        let sites = report
+            .sites
+            .iter()
+            .map(|(name, site)| (name.clone(), AttributeValue::M(site_result_item(site))))
+            .collect();
+
+        let mut item = HashMap::new();
         item.insert(
-            "category".to_string(),
-            AttributeValue::S(metadata.category.clone()),
+            "run_id".to_string(),
+            AttributeValue::S(report.run_id.clone()),
         );
         item.insert(
-            "date_text".to_string(),
-            AttributeValue::S(metadata.date_text.clone()),
+            "service".to_string(),
+            AttributeValue::S(report.service.clone()),
         );
-        item.insert("url".to_string(), AttributeValue::S(metadata.url.clone()));
         item.insert(
-            "html_key".to_string(),
-            AttributeValue::S(metadata.html_key.clone()),
+            "started_at".to_string(),
+            AttributeValue::N(report.started_at.to_string()),
         );
         item.insert(
-            "text_key".to_string(),
-            AttributeValue::S(metadata.text_key.clone()),
+            "finished_at".to_string(),
+            AttributeValue::N(report.finished_at.to_string()),
         );
         item.insert(
-            "images_key".to_string(),
-            AttributeValue::S(metadata.images_key.clone()),
+            "new_articles".to_string(),
+            AttributeValue::N(report.new_articles.to_string()),
         );
         item.insert(
-            "images".to_string(),
-            AttributeValue::L(
-                metadata
-                    .images
-                    .iter()
-                    .cloned()
-                    .map(AttributeValue::S)
-                    .collect(),
-            ),
+            "skipped".to_string(),
+            AttributeValue::N(report.skipped.to_string()),
         );
         item.insert(
-            "updated_at".to_string(),
-            AttributeValue::N(metadata.updated_at.to_string()),
+            "errored".to_string(),
+            AttributeValue::N(report.errored.to_string()),
         );
+        item.insert("sites".to_string(), AttributeValue::M(sites));
+        item.insert("errors".to_string(), string_list(&report.errors));
 
         self.dynamo
             .put_item()
-            .table_name(&self.table_name)
+            .table_name(&self.runs_table_name)
             .set_item(Some(item))
             .send()
             .await?;
 
-        if exists {
-            info!(
-                "TAG:DYNAMO_UPSERT updated article metadata id={} parser={} title=\"{}\"",
-                metadata.id, metadata.parser, metadata.title
-            );
-        } else {
-            info!(
-                "TAG:DYNAMO_UPSERT created article metadata id={} parser={} title=\"{}\"",
-                metadata.id, metadata.parser, metadata.title
-            );
-        }
+        info!(
+            "TAG:SCRAPE_RUN saved run_id={} new={} skipped={} errored={}",
+            report.run_id, report.new_articles, report.skipped, report.errored
+        );
 
         Ok(())
     }
 
-    async fn metadata_exists(&self, id: &str) -> Result<bool> {
-        let result = self
+    /// `slug` if no other article owns it, otherwise `slug` suffixed with part of `id`.
+    /// While `slug-index` is missing or still backfilling, DynamoDB answers with a
+    /// `ValidationException`; the slug is then used unchecked rather than failing the save.
+    async fn unique_slug(&self, id: &str, slug: &str) -> Result<String> {
+        let owners = match self
             .dynamo
-            .get_item()
+            .query()
             .table_name(&self.table_name)
-            .key("id", AttributeValue::S(id.to_string()))
+            .index_name(SLUG_INDEX)
+            .key_condition_expression("slug = :slug")
+            .expression_attribute_values(":slug", AttributeValue::S(slug.to_string()))
+            .projection_expression("id")
             .send()
-            .await?;
+            .await
+        {
+            Ok(owners) => owners,
+            Err(e) if e.code() == Some("ValidationException") => {
+                warn!(
+                    "TAG:SLUG_INDEX_UNAVAILABLE {} not queryable ({}); using slug={} unchecked for id={}",
+                    SLUG_INDEX,
+                    e.message().unwrap_or_default(),
+                    slug,
+                    id
+                );
+                return Ok(slug.to_string());
+            }
+            Err(e) => {
+                return Err(e).with_context(|| format!("failed to look up slug {}", slug));
+            }
+        };
 
-        Ok(result.item().is_some())
+        let taken = owners.items().iter().any(|item| {
+            item.get("id")
+                .and_then(|v| v.as_s().ok())
+                .map(String::as_str)
+                != Some(id)
+        });
+        if taken {
+            let unique = disambiguate_slug(slug, id);
+            info!(
+                "TAG:SLUG_COLLISION slug={} taken; using {} for id={}",
+                slug, unique, id
+            );
+            Ok(unique)
+        } else {
+            Ok(slug.to_string())
+        }
+    }
+
+    async fn download_string(&self, key: &str) -> Result<String> {
+        let obj = self
+            .s3
+            .get_object()
+            .bucket(&self.bucket_name)
+            .key(key)
+            .send()
+            .await
+            .with_context(|| format!("failed to fetch s3://{}/{}", self.bucket_name, key))?;
+        let data = obj
+            .body
+            .collect()
+            .await
+            .context("failed reading s3 object body")?;
+        String::from_utf8(data.into_bytes().to_vec()).context("s3 object was not valid UTF-8")
     }
 
     async fn upload_string(&self, key: &str, data: &str, content_type: &str) -> Result<()> {
         self.upload_bytes(key, data.as_bytes(), content_type).await
     }
 
+    /// Upload raw page HTML to `debug/{host}/{hash}.html` in the snapshot bucket so a
+    /// failed parse can be inspected. Returns the key, or `None` when snapshots are off.
+    pub async fn save_debug_snapshot(&self, url: &str, html: &str) -> Result<Option<String>> {
+        let Some(bucket) = &self.debug_snapshot_bucket else {
+            return Ok(None);
+        };
+
+        let host = reqwest::Url::parse(url)
+            .ok()
+            .and_then(|u| u.host_str().map(|h| h.to_string()))
+            .unwrap_or_else(|| "unknown-host".to_string());
+        let hash = format!("{:x}", Sha256::digest(html.as_bytes()));
+        let key = format!("debug/{}/{}.html", host, &hash[..16]);
+
+        self.s3
+            .put_object()
+            .bucket(bucket)
+            .key(&key)
+            .body(html.as_bytes().to_vec().into())
+            .content_type("text/html")
+            .send()
+            .await
+            .with_context(|| format!("failed to upload debug snapshot s3://{}/{}", bucket, key))?;
+
+        info!("TAG:PARSE_SNAPSHOT url={} key=s3://{}/{}", url, bucket, key);
+        Ok(Some(key))
+    }
+
     async fn upload_bytes(&self, key: &str, data: &[u8], content_type: &str) -> Result<()> {
         self.s3
             .put_object()
@@ -262,7 +791,104 @@ impl Storage {
     }
 }
 
-fn generate_id(parser_name: &str, title: &str, category: &str, date_text: &str) -> String {
+fn metadata_item(metadata: &ArticleMetadataRecord) -> HashMap<String, AttributeValue> {
+    let mut item = HashMap::new();
+    item.insert("id".to_string(), AttributeValue::S(metadata.id.clone()));
+    item.insert(
+        "parser".to_string(),
+        AttributeValue::S(metadata.parser.clone()),
+    );
+    item.insert(
+        "title".to_string(),
+        AttributeValue::S(metadata.title.clone()),
+    );
+    item.insert(
+        "category".to_string(),
+        AttributeValue::S(metadata.category.clone()),
+    );
+    item.insert(
+        "date_text".to_string(),
+        AttributeValue::S(metadata.date_text.clone()),
+    );
+    item.insert("url".to_string(), AttributeValue::S(metadata.url.clone()));
+    item.insert("slug".to_string(), AttributeValue::S(metadata.slug.clone()));
+    item.insert(
+        "html_key".to_string(),
+        AttributeValue::S(metadata.html_key.clone()),
+    );
+    item.insert(
+        "text_key".to_string(),
+        AttributeValue::S(metadata.text_key.clone()),
+    );
+    item.insert(
+        "images_key".to_string(),
+        AttributeValue::S(metadata.images_key.clone()),
+    );
+    item.insert(
+        "images".to_string(),
+        AttributeValue::L(
+            metadata
+                .images
+                .iter()
+                .cloned()
+                .map(AttributeValue::S)
+                .collect(),
+        ),
+    );
+    item.insert(
+        "word_count".to_string(),
+        AttributeValue::N(metadata.word_count.to_string()),
+    );
+    item.insert(
+        "reading_time".to_string(),
+        AttributeValue::S(metadata.reading_time.clone()),
+    );
+    item.insert("tags".to_string(), string_list(&metadata.tags));
+    item.insert(
+        "truncated".to_string(),
+        AttributeValue::Bool(metadata.truncated),
+    );
+    item.insert(
+        "content_hash".to_string(),
+        AttributeValue::S(metadata.content_hash.clone()),
+    );
+    item.insert(
+        "updated_at".to_string(),
+        AttributeValue::N(metadata.updated_at.to_string()),
+    );
+    item
+}
+
+fn site_result_item(site: &SiteResult) -> HashMap<String, AttributeValue> {
+    let mut item = HashMap::new();
+    item.insert("new".to_string(), AttributeValue::N(site.new.to_string()));
+    item.insert(
+        "skipped".to_string(),
+        AttributeValue::N(site.skipped.to_string()),
+    );
+    item.insert("errors".to_string(), string_list(&site.errors));
+    if let Some(listing) = site.listing {
+        item.insert(
+            "listing".to_string(),
+            AttributeValue::S(listing.as_str().to_string()),
+        );
+    }
+    item
+}
+
+fn string_list(values: &[String]) -> AttributeValue {
+    AttributeValue::L(values.iter().cloned().map(AttributeValue::S).collect())
+}
+
+/// Stable metadata id for an article: SHA-256 of its canonical URL, so the same article
+/// fetched from different listing URLs or categories maps to one record.
+pub fn generate_id(canonical_url: &str) -> String {
+    format!("{:x}", Sha256::digest(canonical_url.trim().as_bytes()))
+}
+
+/// The id scheme used before ids were keyed on the canonical URL. Only looked up, so
+/// records saved under it keep their key.
+pub fn legacy_id(parser_name: &str, title: &str, category: &str, date_text: &str) -> String {
     let mut hasher = Sha256::new();
     hasher.update(parser_name.as_bytes());
     hasher.update("|");
@@ -275,19 +901,309 @@ fn generate_id(parser_name: &str, title: &str, category: &str, date_text: &str)
     format!("{:x}", hasher.finalize())
 }
 
-fn slugify(input: &str) -> String {
-    let mut out = String::new();
-    let mut last_dash = false;
-
-    for c in input.to_ascii_lowercase().chars() {
-        if c.is_ascii_alphanumeric() {
-            out.push(c);
-            last_dash = false;
-        } else if !last_dash {
-            out.push('-');
-            last_dash = true;
+/// S3 keys for an article's `content.html`, `content.txt` and `images.json`. Keyed by the
+/// stable id rather than the slug, so articles whose slugs collide never share objects.
+fn content_keys(parser_name: &str, id: &str) -> [String; 3] {
+    ["content.html", "content.txt", "images.json"]
+        .map(|file| format!("{}/{}/{}", parser_name, id, file))
+}
+
+/// Article HTML and text as uploaded: each cut to `max_bytes` on a character boundary.
+/// Cut HTML is re-sanitized so a tag split by the cut is dropped and open elements
+/// are closed; the closing tags may take it slightly past `max_bytes`.
+struct CappedContent<'a> {
+    html: Cow<'a, str>,
+    text: &'a str,
+    truncated: bool,
+}
+
+fn cap_content<'a>(html: &'a str, text: &'a str, max_bytes: usize) -> CappedContent<'a> {
+    let capped_text = truncate_on_char_boundary(text, max_bytes);
+    let capped_html = truncate_on_char_boundary(html, max_bytes);
+    let html_truncated = capped_html.len() < html.len();
+
+    CappedContent {
+        html: if html_truncated {
+            Cow::Owned(sanitize_html(capped_html))
+        } else {
+            Cow::Borrowed(html)
+        },
+        text: capped_text,
+        truncated: html_truncated || capped_text.len() < text.len(),
+    }
+}
+
+/// Send `requests` through `send` in chunks of `BATCH_WRITE_LIMIT`. `send` returns the
+/// requests DynamoDB left unprocessed; those are resent with exponential backoff.
+async fn write_batches<F, Fut>(requests: Vec<WriteRequest>, mut send: F) -> Result<()>
+where
+    F: FnMut(Vec<WriteRequest>) -> Fut,
+    Fut: Future<Output = Result<Vec<WriteRequest>>>,
+{
+    for chunk in requests.chunks(BATCH_WRITE_LIMIT) {
+        let mut pending = chunk.to_vec();
+        let mut attempt = 0;
+        while !pending.is_empty() {
+            if attempt > 0 {
+                tokio::time::sleep(Duration::from_millis(100 * 2u64.pow(attempt))).await;
+            }
+            if attempt >= BATCH_MAX_ATTEMPTS {
+                bail!(
+                    "{} items still unprocessed after {} batch_write_item attempts",
+                    pending.len(),
+                    attempt
+                );
+            }
+
+            pending = send(pending).await?;
+            attempt += 1;
+        }
+    }
+
+    Ok(())
+}
+
+/// `ids` without duplicates (first occurrence kept) split into batches of at most `limit`.
+fn key_batches(ids: &[String], limit: usize) -> Vec<Vec<&str>> {
+    let mut seen = HashSet::new();
+    let unique: Vec<&str> = ids
+        .iter()
+        .map(String::as_str)
+        .filter(|id| seen.insert(*id))
+        .collect();
+    unique.chunks(limit).map(<[&str]>::to_vec).collect()
+}
+
+/// Current and legacy ids a listing entry may already be stored under. The listing URL
+/// stands in for the canonical URL, which is only known once the article is fetched.
+pub fn listing_ids(
+    parser_name: &str,
+    title: &str,
+    category: &str,
+    date_text: &str,
+    url: &str,
+) -> [String; 2] {
+    [
+        generate_id(url),
+        legacy_id(parser_name, title, category, date_text),
+    ]
+}
+
+fn string_attr(item: &HashMap<String, AttributeValue>, name: &str) -> Option<String> {
+    item.get(name).and_then(|v| v.as_s().ok()).cloned()
+}
+
+/// SHA-256 over everything uploaded for an article: HTML, text and image URLs, each
+/// length-prefixed so moving bytes between fields changes the hash.
+fn content_hash(html: &str, text: &str, images: &[String]) -> String {
+    let mut hasher = Sha256::new();
+    for part in [html, text]
+        .into_iter()
+        .chain(images.iter().map(String::as_str))
+    {
+        hasher.update((part.len() as u64).to_le_bytes());
+        hasher.update(part.as_bytes());
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(html: &str, text: &str, images: &[&str]) -> ArticleMetadataRecord {
+        ArticleMetadataRecord::new(
+            "openai_news",
+            "Title",
+            "Product",
+            "2025-01-01",
+            "https://openai.com/index/title/",
+            html,
+            text,
+            "html".to_string(),
+            "text".to_string(),
+            "images".to_string(),
+            images.iter().map(|i| i.to_string()).collect(),
+        )
+    }
+
+    fn put_requests(count: usize) -> Vec<WriteRequest> {
+        (0..count)
+            .map(|i| {
+                let put = PutRequest::builder()
+                    .item("id", AttributeValue::S(format!("id-{}", i)))
+                    .build()
+                    .unwrap();
+                WriteRequest::builder().put_request(put).build()
+            })
+            .collect()
+    }
+
+    fn request_id(request: &WriteRequest) -> String {
+        let item = request.put_request().unwrap().item();
+        item["id"].as_s().unwrap().clone()
+    }
+
+    #[tokio::test]
+    async fn batch_writes_are_chunked_at_the_limit() {
+        let mut sent = Vec::new();
+
+        write_batches(put_requests(60), |batch| {
+            sent.push(batch.iter().map(request_id).collect::<Vec<_>>());
+            async { Ok(Vec::new()) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            sent.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![25, 25, 10]
+        );
+        assert_eq!(sent.concat().len(), 60);
+        assert_eq!(sent[2][9], "id-59");
+    }
+
+    #[tokio::test]
+    async fn unprocessed_items_are_resent() {
+        let mut sent = Vec::new();
+
+        write_batches(put_requests(5), |batch| {
+            sent.push(batch.iter().map(request_id).collect::<Vec<_>>());
+            let unprocessed = if sent.len() == 1 {
+                batch[3..].to_vec()
+            } else {
+                Vec::new()
+            };
+            async move { Ok(unprocessed) }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(sent.len(), 2);
+        assert_eq!(sent[1], vec!["id-3", "id-4"]);
+    }
+
+    #[test]
+    fn colliding_slugs_get_separate_content_keys() {
+        let first = record("<p>First</p>", "First", &[]);
+        let mut second = ArticleMetadataRecord::new(
+            "openai_news",
+            "Title",
+            "Product",
+            "2025-01-01",
+            "https://openai.com/index/title-2/",
+            "<p>Second</p>",
+            "Second",
+            String::new(),
+            String::new(),
+            String::new(),
+            Vec::new(),
+        );
+        assert_eq!(first.slug, second.slug);
+        second.slug = disambiguate_slug(&second.slug, &second.id);
+
+        let first_keys = content_keys("openai_news", &first.id);
+        let second_keys = content_keys("openai_news", &second.id);
+
+        assert_eq!(
+            first_keys[0],
+            format!("openai_news/{}/content.html", first.id)
+        );
+        for key in &first_keys {
+            assert!(!second_keys.contains(key), "{} is shared", key);
+        }
+    }
+
+    #[test]
+    fn oversized_content_is_truncated_and_flagged() {
+        // Multi-byte characters so a naive byte cut would land mid-character.
+        let text = "é".repeat(100);
+        let html = format!("<p>{}</p>", text);
+
+        let content = cap_content(&html, &text, 51);
+
+        assert!(content.truncated);
+        assert_eq!(content.text, "é".repeat(25));
+        assert_eq!(content.html, format!("<p>{}</p>", "é".repeat(24)));
+    }
+
+    #[test]
+    fn truncated_html_never_ends_in_a_broken_tag() {
+        let html =
+            r#"<div><p>Intro text</p><p>More <a href="https://example.com/x">link</a></p></div>"#;
+        let cut = html.find("href").unwrap();
+
+        let content = cap_content(html, "Intro text More link", cut);
+
+        assert!(content.truncated);
+        assert_eq!(content.html, "<div><p>Intro text</p><p>More </p></div>");
+    }
+
+    #[test]
+    fn content_within_the_limit_is_unchanged() {
+        let html = "<p>Short & sweet</p>";
+        let content = cap_content(html, "Short & sweet", 1024);
+
+        assert!(!content.truncated);
+        assert!(matches!(content.html, Cow::Borrowed(h) if h == html));
+        assert_eq!(content.text, "Short & sweet");
+    }
+
+    #[test]
+    fn batch_get_keys_are_deduped_and_chunked_at_the_limit() {
+        let mut ids: Vec<String> = (0..250).map(|i| format!("id-{}", i)).collect();
+        ids.extend(["id-0".to_string(), "id-249".to_string()]);
+
+        let batches = key_batches(&ids, BATCH_GET_LIMIT);
+
+        assert_eq!(
+            batches.iter().map(Vec::len).collect::<Vec<_>>(),
+            vec![100, 100, 50]
+        );
+        assert_eq!(batches[0][0], "id-0");
+        assert_eq!(batches[2][49], "id-249");
+        assert!(key_batches(&[], BATCH_GET_LIMIT).is_empty());
+    }
+
+    #[test]
+    fn identical_content_is_not_rewritten() {
+        let stored = record("<p>Same</p>", "Same", &["https://example.com/a.png"]);
+        let rescraped = record("<p>Same</p>", "Same", &["https://example.com/a.png"]);
+
+        assert_eq!(
+            SaveOutcome::compare(&rescraped.content_hash, Some(&stored.content_hash)),
+            SaveOutcome::NotChanged
+        );
+    }
+
+    #[test]
+    fn changed_or_unhashed_content_is_written() {
+        let stored = record("<p>Same</p>", "Same", &["https://example.com/a.png"]);
+        let changes = [
+            record("<p>Same</p>", "Edited", &["https://example.com/a.png"]),
+            record("<p><b>Same</b></p>", "Same", &["https://example.com/a.png"]),
+            record("<p>Same</p>", "Same", &["https://example.com/b.png"]),
+            record("<p>Same</p>", "Same", &[]),
+        ];
+
+        for rescraped in &changes {
+            assert_eq!(
+                SaveOutcome::compare(&rescraped.content_hash, Some(&stored.content_hash)),
+                SaveOutcome::Written
+            );
         }
+        assert_eq!(
+            SaveOutcome::compare(&stored.content_hash, None),
+            SaveOutcome::Written
+        );
     }
 
-    out.trim_matches('-').to_string()
+    #[test]
+    fn content_hash_separates_fields() {
+        assert_ne!(content_hash("ab", "c", &[]), content_hash("a", "bc", &[]));
+        assert_ne!(
+            content_hash("a", "b", &["c".to_string()]),
+            content_hash("a", "bc", &[])
+        );
+    }
 }
@@ -0,0 +1,83 @@
+use std::collections::HashMap;
+
+/// Number of tags attached to a scraped article.
+pub const DEFAULT_TAG_COUNT: usize = 5;
+
+/// Shortest word considered as a tag; filters out most function words cheaply.
+const MIN_TAG_LEN: usize = 4;
+
+/// Common English words (4+ letters) that never make useful tags.
+const STOPWORDS: &[&str] = &[
+    "about", "above", "after", "again", "against", "also", "among", "another", "because", "been",
+    "before", "being", "below", "between", "both", "could", "does", "doing", "down", "during",
+    "each", "even", "every", "first", "from", "further", "have", "having", "here", "home", "into",
+    "itself", "just", "know", "last", "like", "made", "make", "many", "more", "most", "much",
+    "must", "need", "next", "only", "other", "ours", "over", "same", "should", "since", "some",
+    "such", "than", "that", "their", "theirs", "them", "then", "there", "these", "they", "this",
+    "those", "through", "today", "under", "until", "upon", "used", "using", "very", "want", "well",
+    "were", "what", "when", "where", "which", "while", "will", "with", "within", "without",
+    "would", "year", "years", "your", "yours",
+];
+
+/// Pick up to `limit` keyword tags from `text`: lowercase words of at least four
+/// letters, stopwords removed, ranked by frequency (ties keep first-seen order).
+/// Words seen only once are ignored so short texts don't produce noise tags.
+pub fn extract_tags(text: &str, limit: usize) -> Vec<String> {
+    let mut counts: HashMap<String, (usize, usize)> = HashMap::new();
+
+    for (position, word) in text
+        .split(|c: char| !c.is_alphanumeric() && c != '-')
+        .map(|w| w.trim_matches('-').to_lowercase())
+        .filter(|w| w.chars().count() >= MIN_TAG_LEN)
+        .filter(|w| w.chars().any(|c| c.is_alphabetic()))
+        .filter(|w| !STOPWORDS.contains(&w.as_str()))
+        .enumerate()
+    {
+        counts.entry(word).or_insert((0, position)).0 += 1;
+    }
+
+    let mut ranked: Vec<(String, usize, usize)> = counts
+        .into_iter()
+        .filter(|(_, (count, _))| *count >= 2)
+        .map(|(word, (count, first))| (word, count, first))
+        .collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.2.cmp(&b.2)));
+
+    ranked
+        .into_iter()
+        .take(limit)
+        .map(|(word, _, _)| word)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "Reasoning models can think before they answer. With reasoning, \
+        the models that were trained for safety follow safety specifications more \
+        reliably. These models would also explain their reasoning. Safety evaluations \
+        show reasoning helps, and evaluations with real-world prompts agree.";
+
+    #[test]
+    fn ranks_repeated_keywords_by_frequency() {
+        assert_eq!(
+            extract_tags(SAMPLE, DEFAULT_TAG_COUNT),
+            vec!["reasoning", "models", "safety", "evaluations"]
+        );
+    }
+
+    #[test]
+    fn stopwords_never_become_tags() {
+        let text = "With that, they would have this. With that, they would have this.";
+        assert!(extract_tags(text, DEFAULT_TAG_COUNT).is_empty());
+        assert!(extract_tags(SAMPLE, usize::MAX)
+            .iter()
+            .all(|tag| !STOPWORDS.contains(&tag.as_str())));
+    }
+
+    #[test]
+    fn limit_caps_the_tag_count() {
+        assert_eq!(extract_tags(SAMPLE, 2), vec!["reasoning", "models"]);
+    }
+}
@@ -1,7 +1,44 @@
+use tracing::Subscriber;
+use tracing_subscriber::EnvFilter;
+
+/// Install the global subscriber. `LOG_FORMAT=text` gives plain human-readable lines;
+/// anything else (the default) emits one JSON object per event, including the current
+/// span's fields (e.g. the Lambda `requestId`), for CloudWatch Logs Insights.
 pub fn setup_tracing() {
-    let subscriber = tracing_subscriber::fmt()
-        .with_env_filter(tracing_subscriber::EnvFilter::from_default_env())
-        .json()
-        .finish();
-    tracing::subscriber::set_global_default(subscriber).expect("failed to set tracing subscriber");
+    let text = std::env::var("LOG_FORMAT").is_ok_and(|f| f.eq_ignore_ascii_case("text"));
+    tracing::subscriber::set_global_default(build_subscriber(text))
+        .expect("failed to set tracing subscriber");
+}
+
+/// The subscriber `setup_tracing` installs: plain text lines when `text`, JSON otherwise.
+fn build_subscriber(text: bool) -> Box<dyn Subscriber + Send + Sync> {
+    let builder = tracing_subscriber::fmt().with_env_filter(EnvFilter::from_default_env());
+
+    if text {
+        Box::new(builder.with_ansi(false).finish())
+    } else {
+        Box::new(
+            builder
+                .json()
+                .with_current_span(true)
+                .with_span_list(false)
+                .finish(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subscriber_builds_and_logs_in_both_formats() {
+        for text in [true, false] {
+            tracing::subscriber::with_default(build_subscriber(text), || {
+                let span = tracing::info_span!("request", requestId = "abc-123");
+                let _guard = span.enter();
+                tracing::warn!("TAG:TEST text={}", text);
+            });
+        }
+    }
 }
@@ -0,0 +1,60 @@
+//! Which real HTTP failures count toward opening a crawler backend's breaker.
+
+mod common;
+
+use std::time::Duration;
+
+use anyhow::Context;
+use parser::services::circuit_breaker::{counts_against_backend, BreakerState, CircuitBreaker};
+
+fn respond(_method: &str, path: &str) -> String {
+    match path {
+        "/missing" => common::response("404 Not Found", "text/html", ""),
+        "/busy" => common::response("429 Too Many Requests", "text/html", ""),
+        "/unauthorized" => common::response("401 Unauthorized", "application/json", ""),
+        "/forbidden" => common::response("403 Forbidden", "application/json", ""),
+        _ => common::response("503 Service Unavailable", "text/html", ""),
+    }
+}
+
+async fn get(url: &str) -> anyhow::Result<String> {
+    let res = reqwest::get(url)
+        .await
+        .context("request failed")?
+        .error_for_status()
+        .context("error status")?;
+    Ok(res.text().await?)
+}
+
+#[tokio::test]
+async fn only_server_errors_throttling_and_auth_failures_count() {
+    let base = common::spawn_server(respond);
+
+    let missing = get(&format!("{}/missing", base)).await.unwrap_err();
+    let busy = get(&format!("{}/busy", base)).await.unwrap_err();
+    let down = get(&format!("{}/down", base)).await.unwrap_err();
+    let unauthorized = get(&format!("{}/unauthorized", base)).await.unwrap_err();
+    let forbidden = get(&format!("{}/forbidden", base)).await.unwrap_err();
+
+    assert!(!counts_against_backend(&missing));
+    assert!(counts_against_backend(&busy));
+    assert!(counts_against_backend(&down));
+    assert!(counts_against_backend(&unauthorized));
+    assert!(counts_against_backend(&forbidden));
+}
+
+#[tokio::test]
+async fn dead_links_do_not_open_the_breaker() {
+    let base = common::spawn_server(respond);
+    let breaker = CircuitBreaker::new("test", 2, Duration::from_secs(60));
+
+    for _ in 0..5 {
+        breaker.record_result(&get(&format!("{}/missing", base)).await);
+    }
+    assert_eq!(breaker.state(), BreakerState::Closed);
+
+    for _ in 0..2 {
+        breaker.record_result(&get(&format!("{}/down", base)).await);
+    }
+    assert_eq!(breaker.state(), BreakerState::Open);
+}
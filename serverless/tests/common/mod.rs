@@ -0,0 +1,112 @@
+//! Minimal blocking HTTP/1.1 server for tests that need real `reqwest` responses.
+
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::TcpListener;
+use std::thread;
+
+/// One parsed request. `headers` holds the raw header lines, lowercased, each ending
+/// in `\r\n`.
+#[allow(dead_code)]
+pub struct Request {
+    pub method: String,
+    pub path: String,
+    pub headers: String,
+    pub body: String,
+}
+
+impl Request {
+    /// Value of header `name` (lowercase), if sent.
+    #[allow(dead_code)]
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers.lines().find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            (key == name).then(|| value.trim())
+        })
+    }
+}
+
+/// Serve each connection with the raw response `respond(method, path)` returns
+/// (status line, headers and body), on an ephemeral port. Returns the base URL.
+#[allow(dead_code)]
+pub fn spawn_server(respond: fn(&str, &str) -> String) -> String {
+    spawn_request_server(move |request| respond(&request.method, &request.path))
+}
+
+/// Like `spawn_server`, for responses that depend on the request headers or body.
+pub fn spawn_request_server<F>(respond: F) -> String
+where
+    F: Fn(&Request) -> String + Send + 'static,
+{
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base = format!("http://{}", listener.local_addr().unwrap());
+
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let Ok(mut stream) = stream else { continue };
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut request_line = String::new();
+            if reader.read_line(&mut request_line).is_err() {
+                continue;
+            }
+            // Collect headers up to the blank line.
+            let mut headers = String::new();
+            let mut line = String::new();
+            while reader.read_line(&mut line).unwrap_or(0) > 2 {
+                headers.push_str(&line.to_ascii_lowercase());
+                line.clear();
+            }
+
+            let mut parts = request_line.split_whitespace();
+            let mut request = Request {
+                method: parts.next().unwrap_or_default().to_string(),
+                path: parts.next().unwrap_or_default().to_string(),
+                headers,
+                body: String::new(),
+            };
+            // Read the body too, so closing the socket doesn't reset the response.
+            let length = request
+                .header("content-length")
+                .and_then(|value| value.parse().ok())
+                .unwrap_or(0);
+            let mut body = vec![0; length];
+            if reader.read_exact(&mut body).is_err() {
+                continue;
+            }
+            request.body = String::from_utf8_lossy(&body).into_owned();
+
+            let _ = stream.write_all(respond(&request).as_bytes());
+        }
+    });
+
+    base
+}
+
+/// A `Connection: close` response with the given status, content type and body.
+#[allow(dead_code)]
+pub fn response(status: &str, content_type: &str, body: &str) -> String {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        content_type,
+        body.len(),
+        body
+    )
+}
+
+/// A bodiless `Connection: close` response with the given status, e.g. for HEAD.
+#[allow(dead_code)]
+pub fn empty(status: &str) -> String {
+    format!(
+        "HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        status
+    )
+}
+
+/// A bodiless redirect to `location`.
+#[allow(dead_code)]
+pub fn redirect(status: &str, location: &str) -> String {
+    format!(
+        "HTTP/1.1 {}\r\nLocation: {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n",
+        status, location
+    )
+}
@@ -0,0 +1,69 @@
+//! The fallback crawler's per-site loop against a local server standing in for the
+//! article hosts, DynamoDB and S3. A single test, since it sets process-wide
+//! environment variables.
+
+mod common;
+
+use std::path::PathBuf;
+
+use parser::models::Site;
+use parser::services::fallback_crawler::FallbackCrawlerService;
+
+fn article_html() -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/openai_article/article.html");
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("read {}: {}", path.display(), e))
+}
+
+fn site(name: &str, url: String) -> Site {
+    serde_json::from_value(serde_json::json!({
+        "name": name,
+        "top_articles": null,
+        "provided_listing": [{ "url": url, "title": "", "category": "Research" }],
+    }))
+    .unwrap()
+}
+
+#[tokio::test]
+async fn failed_site_does_not_hide_the_next_one() {
+    let html = article_html();
+    // Site A's host is down; site B's serves the article. Every DynamoDB call finds
+    // nothing stored and every S3 upload succeeds.
+    let base = common::spawn_request_server(move |request| {
+        match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/site-a/post") => {
+                common::response("500 Internal Server Error", "text/html", "")
+            }
+            ("GET", "/site-b/post") => common::response("200 OK", "text/html", &html),
+            ("POST", "/") => common::response("200 OK", "application/x-amz-json-1.0", "{}"),
+            ("PUT", _) => common::empty("200 OK"),
+            _ => common::empty("404 Not Found"),
+        }
+    });
+    std::env::set_var("AWS_ENDPOINT_URL", &base);
+    std::env::set_var("AWS_REGION", "us-east-1");
+    std::env::set_var("AWS_ACCESS_KEY_ID", "test");
+    std::env::set_var("AWS_SECRET_ACCESS_KEY", "test");
+
+    let service = FallbackCrawlerService::new().await.unwrap();
+    let results = service
+        .execute(&[
+            site("site-a", format!("{}/site-a/post", base)),
+            site("site-b", format!("{}/site-b/post", base)),
+        ])
+        .await
+        .unwrap();
+
+    assert_eq!(results.new_articles, 1);
+    assert_eq!(results.per_site["site-b"].new, 1);
+    assert!(results.per_site["site-b"].errors.is_empty());
+
+    assert_eq!(results.per_site["site-a"].new, 0);
+    assert_eq!(results.errors.len(), 1, "{:?}", results.errors);
+    assert!(
+        results.errors[0].starts_with("site-a: failed to fetch provided article"),
+        "{:?}",
+        results.errors
+    );
+    assert_eq!(results.per_site["site-a"].errors.len(), 1);
+}
@@ -0,0 +1,88 @@
+//! Response body caps and content-type checks against a local listener.
+
+mod common;
+
+use parser::http::{is_unexpected_content_type, read_body_capped, read_html, FetchError};
+
+const CAP: usize = 1024;
+
+fn respond(_method: &str, path: &str) -> String {
+    let big = "x".repeat(CAP * 4);
+    match path {
+        "/declared" => common::response("200 OK", "text/html", &big),
+        "/streamed" => {
+            // Chunked, so no Content-Length for the up-front check to see.
+            let chunk = "x".repeat(512);
+            let chunks: String = (0..8)
+                .map(|_| format!("{:x}\r\n{}\r\n", chunk.len(), chunk))
+                .collect();
+            format!(
+                "HTTP/1.1 200 OK\r\nContent-Type: text/html\r\nTransfer-Encoding: chunked\r\nConnection: close\r\n\r\n{}0\r\n\r\n",
+                chunks
+            )
+        }
+        "/report.pdf" => common::response("200 OK", "application/pdf", "%PDF-1.7"),
+        "/xhtml" => common::response("200 OK", "application/xhtml+xml; charset=utf-8", "<p>x</p>"),
+        _ => common::response("200 OK", "text/html", "<p>small</p>"),
+    }
+}
+
+fn is_too_large(err: &anyhow::Error) -> bool {
+    matches!(
+        err.downcast_ref::<FetchError>(),
+        Some(FetchError::BodyTooLarge { limit: CAP, .. })
+    )
+}
+
+#[tokio::test]
+async fn rejects_body_over_declared_content_length() {
+    let base = common::spawn_server(respond);
+    let res = reqwest::get(format!("{}/declared", base)).await.unwrap();
+
+    let err = read_body_capped(res, CAP).await.unwrap_err();
+
+    assert!(is_too_large(&err), "{:?}", err);
+}
+
+#[tokio::test]
+async fn rejects_streamed_body_once_it_passes_the_cap() {
+    let base = common::spawn_server(respond);
+    let res = reqwest::get(format!("{}/streamed", base)).await.unwrap();
+    assert!(res.content_length().is_none());
+
+    let err = read_body_capped(res, CAP).await.unwrap_err();
+
+    assert!(is_too_large(&err), "{:?}", err);
+}
+
+#[tokio::test]
+async fn reads_body_under_the_cap() {
+    let base = common::spawn_server(respond);
+    let res = reqwest::get(format!("{}/small", base)).await.unwrap();
+
+    assert_eq!(read_body_capped(res, CAP).await.unwrap(), "<p>small</p>");
+}
+
+#[tokio::test]
+async fn rejects_pdf_response_as_unexpected_content_type() {
+    let base = common::spawn_server(respond);
+    let res = reqwest::get(format!("{}/report.pdf", base)).await.unwrap();
+
+    let err = read_html(res).await.unwrap_err();
+
+    assert!(is_unexpected_content_type(&err), "{:?}", err);
+    assert!(matches!(
+        err.downcast_ref::<FetchError>(),
+        Some(FetchError::UnexpectedContentType { content_type, .. }) if content_type == "application/pdf"
+    ));
+}
+
+#[tokio::test]
+async fn accepts_html_and_xhtml_responses() {
+    let base = common::spawn_server(respond);
+
+    for path in ["/small", "/xhtml"] {
+        let res = reqwest::get(format!("{}{}", base, path)).await.unwrap();
+        assert!(read_html(res).await.is_ok(), "{}", path);
+    }
+}
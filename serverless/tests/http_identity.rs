@@ -0,0 +1,43 @@
+//! The shared client's user agent and `From` header, as seen by a local listener.
+//! A single test, since it sets process-wide environment variables.
+
+mod common;
+
+use parser::http::build_http_client;
+
+/// Serve each connection with a response whose body is the request's header lines.
+fn spawn_header_echo_server() -> String {
+    common::spawn_request_server(|request| {
+        common::response("200 OK", "text/plain", &request.headers)
+    })
+}
+
+async fn sent_headers(base: &str) -> String {
+    let client = build_http_client().expect("client should build");
+    client.get(base).send().await.unwrap().text().await.unwrap()
+}
+
+#[tokio::test]
+async fn configured_identity_is_sent_and_bad_contact_is_dropped() {
+    let base = spawn_header_echo_server();
+    std::env::set_var(
+        "SCRAPER_USER_AGENT",
+        "TestAgent/2.0 (+https://example.com/bot)",
+    );
+    std::env::set_var("SCRAPER_CONTACT_EMAIL", " ops@example.com ");
+
+    let headers = sent_headers(&base).await;
+    assert!(
+        headers.contains("user-agent: testagent/2.0 (+https://example.com/bot)\r\n"),
+        "{}",
+        headers
+    );
+    assert!(headers.contains("from: ops@example.com\r\n"), "{}", headers);
+
+    std::env::set_var("SCRAPER_CONTACT_EMAIL", "ops@example.com\nX-Injected: 1");
+
+    let headers = sent_headers(&base).await;
+    assert!(headers.contains("user-agent: testagent/2.0"), "{}", headers);
+    assert!(!headers.contains("from:"), "{}", headers);
+    assert!(!headers.contains("x-injected"), "{}", headers);
+}
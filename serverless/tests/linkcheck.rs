@@ -0,0 +1,51 @@
+//! Link check classification against a local HTTP server that answers by path.
+
+mod common;
+
+use std::net::TcpListener;
+use std::time::Duration;
+
+use parser::services::linkcheck::{check_source_url, linkcheck_client, SourceStatus};
+
+/// `/ok` (200), `/moved` (301), `/gone` (404) and `/head-unsupported` (405 for HEAD,
+/// 200 for GET).
+fn respond(method: &str, path: &str) -> String {
+    match (method, path) {
+        (_, "/ok") => common::empty("200 OK"),
+        (_, "/moved") => common::redirect("301 Moved Permanently", "/ok"),
+        ("HEAD", "/head-unsupported") => common::empty("405 Method Not Allowed"),
+        ("GET", "/head-unsupported") => common::empty("200 OK"),
+        _ => common::empty("404 Not Found"),
+    }
+}
+
+#[tokio::test]
+async fn classifies_source_responses() {
+    let base = common::spawn_server(respond);
+    let client = linkcheck_client(Duration::from_secs(5)).unwrap();
+
+    let cases = [
+        ("/ok", SourceStatus::Ok),
+        ("/moved", SourceStatus::Redirect),
+        ("/gone", SourceStatus::Dead),
+        ("/head-unsupported", SourceStatus::Ok),
+    ];
+    for (path, expected) in cases {
+        let url = format!("{}{}", base, path);
+        assert_eq!(check_source_url(&client, &url).await, expected, "{}", path);
+    }
+}
+
+#[tokio::test]
+async fn unreachable_source_is_dead() {
+    // Bind then drop a listener so the port is closed.
+    let addr = TcpListener::bind("127.0.0.1:0")
+        .unwrap()
+        .local_addr()
+        .unwrap();
+    let client = linkcheck_client(Duration::from_secs(5)).unwrap();
+
+    let status = check_source_url(&client, &format!("http://{}/", addr)).await;
+
+    assert_eq!(status, SourceStatus::Dead);
+}
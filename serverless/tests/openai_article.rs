@@ -0,0 +1,84 @@
+//! Saved-page tests for the OpenAI article parser, run offline through
+//! `Parser::parse_article_from_html`.
+
+use std::path::PathBuf;
+
+use parser::parsers::openai_research::OpenAIResearchParser;
+use parser::parsers::Parser;
+use parser::storage::generate_id;
+
+fn fixture(name: &str) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/openai_article")
+        .join(name);
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("read {}: {}", path.display(), e))
+}
+
+#[test]
+fn parses_saved_article_page() {
+    let article = OpenAIResearchParser::new()
+        .parse_article_from_html(
+            &fixture("article.html"),
+            "https://openai.com/index/deliberative-alignment/?utm_source=feed",
+        )
+        .unwrap();
+
+    assert_eq!(
+        article.canonical_url,
+        "https://openai.com/index/deliberative-alignment/"
+    );
+    assert_eq!(article.title, "Deliberative alignment");
+    assert_eq!(article.author, "OpenAI Research");
+    assert_eq!(article.published_date, "2024-12-20T18:00:00.000Z");
+    assert_eq!(
+        article.images,
+        vec![
+            "https://images.openai.com/hero.png",
+            "https://openai.com/images/diagram.png",
+        ]
+    );
+    assert_eq!(
+        article.content_text,
+        "Deliberative alignment: reasoning enables safer language models Dec 19, 2024 \
+         We introduce deliberative alignment, a training paradigm. The training pipeline. \
+         Models reason over safety specifications ."
+    );
+    assert!(!article.content_html.contains("<script"));
+    assert!(!article.content_html.contains("onerror"));
+    assert!(!article.content_html.contains("onclick"));
+    assert!(article.content_html.contains("The training pipeline."));
+}
+
+#[test]
+fn page_without_content_is_an_error() {
+    let result = OpenAIResearchParser::new().parse_article_from_html(
+        "<html><body><nav>Nothing here</nav></body></html>",
+        "https://openai.com/index/missing/",
+    );
+
+    assert!(result.is_err());
+}
+
+#[test]
+fn fetched_urls_sharing_a_canonical_url_share_an_id() {
+    let parser = OpenAIResearchParser::new();
+    let html = fixture("article.html");
+    let from_listing = parser
+        .parse_article_from_html(&html, "https://openai.com/index/deliberative-alignment/")
+        .unwrap();
+    let syndicated = parser
+        .parse_article_from_html(
+            &html,
+            "https://openai.com/news/safety/deliberative-alignment-copy/",
+        )
+        .unwrap();
+
+    assert_eq!(
+        generate_id(&from_listing.canonical_url),
+        generate_id(&syndicated.canonical_url)
+    );
+    assert_ne!(
+        generate_id(&from_listing.canonical_url),
+        generate_id("https://openai.com/index/another-article/")
+    );
+}
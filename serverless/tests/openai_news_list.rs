@@ -0,0 +1,129 @@
+//! Golden-file tests for `parse_openai_news_list`: each `fixtures/openai_news_list/*.html`
+//! page must parse to exactly the articles in the matching `*.json` file.
+
+use std::path::PathBuf;
+
+use parser::models::ListingOutcome;
+use parser::parsers::selectors::NewsListSelectors;
+use parser::parsers::{parse_openai_news_list_with, OPENAI_BASE};
+use serde_json::Value;
+
+fn fixture(name: &str) -> String {
+    let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures/openai_news_list")
+        .join(name);
+    std::fs::read_to_string(&path).unwrap_or_else(|e| panic!("read {}: {}", path.display(), e))
+}
+
+fn assert_golden(name: &str) {
+    let html = fixture(&format!("{}.html", name));
+    let expected: Value = serde_json::from_str(&fixture(&format!("{}.json", name))).unwrap();
+
+    let (articles, outcome) =
+        parse_openai_news_list_with(&html, OPENAI_BASE, &NewsListSelectors::default());
+
+    assert_eq!(outcome, ListingOutcome::Found, "{}", name);
+    assert_eq!(
+        serde_json::to_value(&articles).unwrap(),
+        expected,
+        "{}",
+        name
+    );
+}
+
+#[test]
+fn parses_current_listing_markup() {
+    assert_golden("listing");
+}
+
+#[test]
+fn handles_missing_time_element() {
+    assert_golden("missing_time");
+}
+
+#[test]
+fn resolves_relative_hrefs_against_base() {
+    assert_golden("relative_href");
+}
+
+#[test]
+fn reports_selector_miss_when_rows_are_absent() {
+    let (articles, outcome) = parse_openai_news_list_with(
+        "<html><body><ul><li>Not a listing</li></ul></body></html>",
+        OPENAI_BASE,
+        &NewsListSelectors::default(),
+    );
+
+    assert!(articles.is_empty());
+    assert_eq!(outcome, ListingOutcome::SelectorMiss);
+}
+
+#[test]
+fn custom_selectors_override_the_defaults() {
+    let html = r#"<html><body><ul class="posts">
+      <li class="entry">
+        <span class="meta"><b>Safety</b><time datetime="2025-02-01T00:00:00Z">Feb 1, 2025</time></span>
+        <a class="permalink" href="/index/custom-markup/">
+          <h3>Custom markup</h3><p class="blurb">Rows no longer use the grid layout.</p>
+        </a>
+      </li>
+    </ul></body></html>"#;
+    let selectors: NewsListSelectors = serde_json::from_str(
+        r#"{"row": "ul.posts > li.entry", "meta": "span.meta", "category": "b",
+            "link": "a.permalink", "title": "h3", "summary": "p.blurb"}"#,
+    )
+    .unwrap();
+
+    let (defaults, _) =
+        parse_openai_news_list_with(html, OPENAI_BASE, &NewsListSelectors::default());
+    let (articles, outcome) = parse_openai_news_list_with(html, OPENAI_BASE, &selectors);
+
+    assert!(defaults.is_empty());
+    assert_eq!(selectors.time, NewsListSelectors::default().time);
+    assert_eq!(outcome, ListingOutcome::Found);
+    assert_eq!(articles.len(), 1);
+    assert_eq!(articles[0].category, "Safety");
+    assert_eq!(
+        articles[0].date_iso.as_deref(),
+        Some("2025-02-01T00:00:00Z")
+    );
+    assert_eq!(articles[0].title, "Custom markup");
+    assert_eq!(articles[0].summary, "Rows no longer use the grid layout.");
+    assert_eq!(articles[0].url, "https://openai.com/index/custom-markup/");
+}
+
+#[test]
+fn invalid_selector_falls_back_to_the_default() {
+    let selectors = NewsListSelectors {
+        title: "[[not a selector".to_string(),
+        ..NewsListSelectors::default()
+    };
+
+    let (articles, outcome) =
+        parse_openai_news_list_with(&fixture("listing.html"), OPENAI_BASE, &selectors);
+    let (expected, _) = parse_openai_news_list_with(
+        &fixture("listing.html"),
+        OPENAI_BASE,
+        &NewsListSelectors::default(),
+    );
+
+    assert_eq!(outcome, ListingOutcome::Found);
+    assert_eq!(
+        serde_json::to_value(&articles).unwrap(),
+        serde_json::to_value(&expected).unwrap()
+    );
+}
+
+#[test]
+fn reports_empty_when_rows_match_but_yield_no_items() {
+    let html = r#"<html><body><div class="grid">
+      <div class="py-md"><div class="text-meta"><div>Product</div></div></div>
+      <div class="py-md"><a href="/index/untitled/"><p class="text-p2">No title here.</p></a></div>
+    </div></body></html>"#;
+
+    let (articles, outcome) =
+        parse_openai_news_list_with(html, OPENAI_BASE, &NewsListSelectors::default());
+
+    assert!(articles.is_empty());
+    assert_eq!(outcome, ListingOutcome::Empty);
+}